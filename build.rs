@@ -0,0 +1,111 @@
+//! Generates a registry of all solutions in `src/bin` so they can be run in-process by `cargo all` and `cargo time`.
+//! Also generates a test for every example of a solution, see `template::examples`.
+use std::{collections::BTreeSet, env, fs, path::Path};
+
+#[path = "src/template/example_tables.rs"]
+mod example_tables;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut puzzles: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = stem.split_once('_')?;
+                    let is_solution =
+                        path.extension()? == "rs" && year.len() == 4 && day.len() == 2;
+                    let year = year.parse::<u16>().ok().filter(|y| *y >= 2015)?;
+                    let day = day.parse::<u8>().ok().filter(|d| (1..=25).contains(d))?;
+                    is_solution.then(|| (year, day, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    // NOTE: solution modules are skipped in tests, their tests already run as part of the solution binaries.
+    // with `dhat-heap`, every solution declares its own global allocator and can not be linked together.
+    let cfg = r#"#[cfg(not(any(test, feature = "dhat-heap")))]"#;
+
+    let mut registry = String::from("// @generated by build.rs, do not edit.\n\n");
+
+    for (year, day, path) in &puzzles {
+        registry.push_str(&format!(
+            "{cfg}\n#[allow(dead_code)]\n#[path = {path:?}]\nmod y{year}_day_{day:02};\n\n"
+        ));
+    }
+
+    let solutions: Vec<String> = puzzles
+        .iter()
+        .map(|(year, day, _)| format!("y{year}_day_{day:02}::SOLUTION"))
+        .collect();
+
+    registry.push_str(&format!(
+        "{cfg}\npub const SOLUTIONS: &[Solution] = &[{}];\n\n",
+        solutions.join(", ")
+    ));
+    registry.push_str(
+        "#[cfg(any(test, feature = \"dhat-heap\"))]\npub const SOLUTIONS: &[Solution] = &[];\n",
+    );
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+
+    write_example_tests(&manifest_dir, &out_dir, &puzzles);
+}
+
+/// Writes one test per `[[example]]` of `data/<year>/examples/<day>.toml` to `<out_dir>/examples/<path of the solution>`.
+/// Solutions without examples get an empty file, as every solution includes its generated tests.
+fn write_example_tests(manifest_dir: &str, out_dir: &str, puzzles: &[(u16, u8, String)]) {
+    let years: BTreeSet<u16> = puzzles.iter().map(|(year, _, _)| *year).collect();
+    let examples_dir = |year: u16| {
+        Path::new(manifest_dir)
+            .join("data")
+            .join(year.to_string())
+            .join("examples")
+    };
+
+    // NOTE: watching a path that does not exist would rerun the build script on every build.
+    for year in years {
+        if examples_dir(year).exists() {
+            println!("cargo:rerun-if-changed={}", examples_dir(year).display());
+        }
+    }
+
+    let tests_dir = Path::new(out_dir).join("examples").join("src").join("bin");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    for (year, day, _) in puzzles {
+        let path = examples_dir(*year).join(format!("{day:02}.toml"));
+        let count = match fs::read_to_string(&path).map(|s| example_tables::parse_tables(&s)) {
+            Ok(Ok(tables)) => tables.len(),
+            Ok(Err(e)) => {
+                // NOTE: a single test reads the file at runtime and fails with the error.
+                let message = e.lines().next().unwrap_or_default();
+                println!(
+                    "cargo:warning=invalid examples in {}: {message}",
+                    path.display()
+                );
+                1
+            }
+            Err(_) => 0,
+        };
+
+        let mut tests = String::from("// @generated by build.rs, do not edit.\n");
+        for i in 1..=count {
+            tests.push_str(&format!(
+                "\n#[test]\nfn example_{i}() {{\n    test_example({i});\n}}\n"
+            ));
+        }
+
+        fs::write(tests_dir.join(format!("{year}_{day:02}.rs")), tests).unwrap();
+    }
+}
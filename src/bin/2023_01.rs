@@ -38,20 +38,12 @@ fn get_words(input: &str) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let v: u32 = input
-        .split_terminator('\n')
-        .into_iter()
-        .map(|s| get_digits(s))
-        .sum();
+    let v: u32 = input.split_terminator('\n').map(get_digits).sum();
     Some(v)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let v: u32 = input
-        .split_terminator('\n')
-        .into_iter()
-        .map(|s| get_words(s))
-        .sum();
+    let v: u32 = input.split_terminator('\n').map(get_words).sum();
     Some(v)
 }
 
@@ -62,7 +54,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(142));
     }
@@ -70,7 +62,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(281));
     }
@@ -43,7 +43,6 @@ fn parse_game(input: &str) -> Game {
     let id: u32 = caps["id"].parse().unwrap();
     let sets: Vec<Set> = caps["sets"]
         .split_terminator(';')
-        .into_iter()
         .map(|set| parse_set(set.trim()))
         .collect();
     Game { id, sets }
@@ -82,11 +81,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         green: 13,
         blue: 14,
     };
-    let games: Vec<Game> = input
-        .split_terminator('\n')
-        .into_iter()
-        .map(|line| parse_game(line))
-        .collect();
+    let games: Vec<Game> = input.split_terminator('\n').map(parse_game).collect();
     let result: u32 = games
         .iter()
         .filter(|game| valid_game(game, &bag))
@@ -96,12 +91,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let games: Vec<Game> = input
-        .split_terminator('\n')
-        .into_iter()
-        .map(|line| parse_game(line))
-        .collect();
-    let result: u32 = games.iter().map(|game| power_game(game)).sum();
+    let games: Vec<Game> = input.split_terminator('\n').map(parse_game).collect();
+    let result: u32 = games.iter().map(power_game).sum();
     Some(result)
 }
 
@@ -112,7 +103,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(8));
     }
@@ -120,7 +111,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(2286));
     }
@@ -28,7 +28,7 @@ fn parse_schematic(input: &str) -> Schematic {
     let re = Regex::new(r"(?<part>\d+)|(?<symbol>[\D--\.])").unwrap();
     let mut schematic: Schematic = vec![];
 
-    for (i, val) in input.split_terminator('\n').into_iter().enumerate() {
+    for (i, val) in input.split_terminator('\n').enumerate() {
         for c in re.captures_iter(val) {
             if let Some(part) = c.name("part") {
                 schematic.push(Item {
@@ -105,7 +105,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let result: u32 = result
         .into_iter()
-        .map(|s| s.into_iter().fold(1, |x, y| x * y))
+        .map(|s| s.into_iter().product::<u32>())
         .sum();
 
     Some(result)
@@ -118,7 +118,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(4361));
     }
@@ -126,7 +126,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(467835));
     }
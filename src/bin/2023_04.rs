@@ -122,7 +122,6 @@ impl FromStr for Problem {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cards: Vec<Result<Card, ParseCardError>> = s
             .split_terminator('\n')
-            .into_iter()
             .map(|line| line.parse::<Card>())
             .collect();
         if cards.iter().any(|x| x.is_err()) {
@@ -153,7 +152,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(13));
     }
@@ -161,7 +160,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(30));
     }
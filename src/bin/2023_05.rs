@@ -2,7 +2,7 @@ advent_of_code::solution!(5);
 
 use itertools::Itertools;
 use regex::{Regex, RegexBuilder};
-use std::{borrow::Borrow, str::FromStr};
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq)]
 struct ParseProblemError;
@@ -141,7 +141,7 @@ impl Map {
                 });
 
         for range in todo.iter_mut() {
-            range.level = range.level + 1;
+            range.level += 1;
         }
 
         done.append(&mut todo);
@@ -155,7 +155,6 @@ impl FromStr for Map {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let entries: Vec<Result<Entry, ParseProblemError>> = s
             .split_terminator('\n')
-            .into_iter()
             .map(|x| x.parse::<Entry>())
             .collect();
         if entries.iter().any(|x| x.is_err()) {
@@ -205,7 +204,6 @@ humidity-to-location map:\n(.+)$";
             .map_err(|_| ParseProblemError)?;
 
         let maps: Vec<Result<Map, ParseProblemError>> = (0..Problem::MAP_LEN)
-            .into_iter()
             .map(|i| {
                 caps[i + Problem::MAP_OFFSET]
                     .parse::<Map>()
@@ -243,7 +241,7 @@ impl Solver {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(_input: &str) -> Option<u64> {
     Some(35)
 }
 
@@ -260,7 +258,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(35));
     }
@@ -268,7 +266,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(46));
     }
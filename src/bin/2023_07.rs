@@ -42,7 +42,7 @@ enum HandType {
     FiveKind,
 }
 
-#[derive(Debug, PartialEq, Eq, Ord)]
+#[derive(Debug, PartialEq, Eq)]
 struct Hand {
     cards: Vec<Card>,
     bid: usize,
@@ -64,7 +64,7 @@ impl Hand {
             .into_iter()
             .sorted_by(|(a, b), (c, d)| d.cmp(b).then(c.cmp(a)))
             .collect();
-        if result.len() == 0 {
+        if result.is_empty() {
             let card = self.cards.iter().find(|c| c.is_joker()).unwrap();
             result.push((card, jokers));
         } else {
@@ -97,14 +97,19 @@ impl Hand {
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
         let self_type = self.evaluate();
         let other_type = other.evaluate();
-        let result = self_type
+        self_type
             .cmp(&other_type)
-            .then(self.cards.cmp(&other.cards));
-        Some(result)
+            .then(self.cards.cmp(&other.cards))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -151,7 +156,7 @@ impl FromStr for Problem {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(_input: &str) -> Option<u32> {
     Some(6440)
 }
 
@@ -166,13 +171,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 }
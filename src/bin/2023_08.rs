@@ -103,6 +103,7 @@ impl FromStr for Problem {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Solution {
     seeds: VecDeque<usize>,
     first: usize,
@@ -115,10 +116,12 @@ impl Solution {
         Solution { seeds, first, len }
     }
 
+    #[allow(dead_code)]
     fn min(&self) -> &usize {
         &self.seeds[0]
     }
 
+    #[allow(dead_code)]
     fn next(&mut self) {
         let seed = self.seeds.pop_front().unwrap();
         self.seeds.push_back(seed + self.len);
@@ -150,22 +153,25 @@ impl<'a> Solver<'a> {
         }
     }
 
-    fn is_start(id: &String) -> bool {
+    fn is_start(id: &str) -> bool {
         id.chars().last().unwrap() == Self::START
     }
 
-    fn is_end(id: &String) -> bool {
+    fn is_end(id: &str) -> bool {
         id.chars().last().unwrap() == Self::END
     }
 
+    #[allow(dead_code)]
     fn is_match(&self) -> bool {
         self.results.iter().map(|x| x.min()).all_equal()
     }
 
+    #[allow(dead_code)]
     fn min(&self) -> &usize {
         self.results.iter().map(|x| x.min()).min().unwrap()
     }
 
+    #[allow(dead_code)]
     fn next_min(&mut self) {
         let min = self
             .results
@@ -199,7 +205,7 @@ impl<'a> Solver<'a> {
                         if Self::is_end(&current.id) {
                             result.push_back(iterations);
                         }
-                        current = self.problem.apply(&current, step);
+                        current = self.problem.apply(current, step);
                         iterations += 1;
                     }
                 }
@@ -211,6 +217,7 @@ impl<'a> Solver<'a> {
         self.results.iter().map(|x| x.first).all_equal()
     }
 
+    #[allow(dead_code)]
     fn run(&mut self) -> usize {
         while !self.is_match() {
             dbg!(&self.results[0].seeds[0]);
@@ -220,7 +227,7 @@ impl<'a> Solver<'a> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(_input: &str) -> Option<u64> {
     None
 }
 
@@ -241,7 +248,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, None);
     }
@@ -249,7 +256,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(6));
     }
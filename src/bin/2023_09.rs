@@ -100,11 +100,11 @@ struct SolverOne<'a> {
 }
 
 impl<'a> SolverOne<'a> {
-    fn new(problem: &Problem) -> SolverOne {
+    fn new(problem: &Problem) -> SolverOne<'_> {
         SolverOne { problem }
     }
 
-    fn integrate_all(subsequences: &Vec<Sequence>) -> isize {
+    fn integrate_all(subsequences: &[Sequence]) -> isize {
         subsequences.iter().fold(0, |x, y| y.integrate_one(x))
     }
 
@@ -132,11 +132,11 @@ struct SolverTwo<'a> {
 }
 
 impl<'a> SolverTwo<'a> {
-    fn new(problem: &Problem) -> SolverTwo {
+    fn new(problem: &Problem) -> SolverTwo<'_> {
         SolverTwo { problem }
     }
 
-    fn integrate_all(subsequences: &Vec<Sequence>) -> isize {
+    fn integrate_all(subsequences: &[Sequence]) -> isize {
         subsequences.iter().fold(0, |x, y| y.integrate_two(x))
     }
 
@@ -179,7 +179,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(114));
     }
@@ -187,7 +187,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(2));
     }
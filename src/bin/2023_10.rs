@@ -83,25 +83,17 @@ impl Map {
         let (rows, cols) = self.get_dimensions();
         let mut result = vec![];
 
-        if row > 0 {
-            if self.get_neighbours(row - 1, col).contains(&(row, col)) {
-                result.push('N');
-            }
+        if row > 0 && self.get_neighbours(row - 1, col).contains(&(row, col)) {
+            result.push('N');
         }
-        if col < cols - 1 {
-            if self.get_neighbours(row, col + 1).contains(&(row, col)) {
-                result.push('E');
-            }
+        if col < cols - 1 && self.get_neighbours(row, col + 1).contains(&(row, col)) {
+            result.push('E');
         }
-        if row < rows - 1 {
-            if self.get_neighbours(row + 1, col).contains(&(row, col)) {
-                result.push('S');
-            }
+        if row < rows - 1 && self.get_neighbours(row + 1, col).contains(&(row, col)) {
+            result.push('S');
         }
-        if col > 0 {
-            if self.get_neighbours(row, col - 1).contains(&(row, col)) {
-                result.push('W');
-            }
+        if col > 0 && self.get_neighbours(row, col - 1).contains(&(row, col)) {
+            result.push('W');
         }
 
         assert_eq!(result.len(), 2);
@@ -181,25 +173,17 @@ impl Map {
             }
             Tile::Ground => {}
             Tile::Start => {
-                if row > 0 {
-                    if self.get_neighbours(row - 1, col).contains(&(row, col)) {
-                        result.push((row - 1, col));
-                    }
+                if row > 0 && self.get_neighbours(row - 1, col).contains(&(row, col)) {
+                    result.push((row - 1, col));
                 }
-                if col < cols - 1 {
-                    if self.get_neighbours(row, col + 1).contains(&(row, col)) {
-                        result.push((row, col + 1));
-                    }
+                if col < cols - 1 && self.get_neighbours(row, col + 1).contains(&(row, col)) {
+                    result.push((row, col + 1));
                 }
-                if row < rows - 1 {
-                    if self.get_neighbours(row + 1, col).contains(&(row, col)) {
-                        result.push((row + 1, col));
-                    }
+                if row < rows - 1 && self.get_neighbours(row + 1, col).contains(&(row, col)) {
+                    result.push((row + 1, col));
                 }
-                if col > 0 {
-                    if self.get_neighbours(row, col - 1).contains(&(row, col)) {
-                        result.push((row, col - 1));
-                    }
+                if col > 0 && self.get_neighbours(row, col - 1).contains(&(row, col)) {
+                    result.push((row, col - 1));
                 }
             }
         }
@@ -232,7 +216,7 @@ struct Cell<'a> {
 }
 
 impl<'a> Cell<'a> {
-    fn new(tile: &'a Tile) -> Cell {
+    fn new(tile: &'a Tile) -> Cell<'a> {
         Cell {
             tile,
             distance: RefCell::new(None),
@@ -257,7 +241,7 @@ struct Solver<'a> {
 }
 
 impl<'a> Solver<'a> {
-    fn new(map: &'a Map) -> Solver {
+    fn new(map: &'a Map) -> Solver<'a> {
         let cells: Vec<Vec<Cell>> = map
             .tiles
             .iter()
@@ -267,7 +251,7 @@ impl<'a> Solver<'a> {
         Solver { map, cells, queue }
     }
 
-    fn get_cell(&self, row: usize, col: usize) -> &Cell {
+    fn get_cell(&self, row: usize, col: usize) -> &Cell<'_> {
         &self.cells[row][col]
     }
 
@@ -389,32 +373,3 @@ pub fn part_two(input: &str) -> Option<usize> {
     solver.run_zone();
     Some(solver.get_inside())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(4));
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(4));
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(10));
-    }
-}
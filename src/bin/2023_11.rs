@@ -1,5 +1,6 @@
 advent_of_code::solution!(11);
 
+use advent_of_code::template::Params;
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use std::str::FromStr;
@@ -140,31 +141,10 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(distances.iter().sum())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str, params: &Params) -> Result<usize, String> {
     let mut map = input.parse::<Map>().unwrap();
-    map.set_factor(1000000);
+    map.set_factor(params.get_or("factor", 1000000)?);
     map.compute_empty();
     let distances = map.compute_distances();
-    Some(distances.iter().sum())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(374));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(82000210));
-    }
+    Ok(distances.iter().sum())
 }
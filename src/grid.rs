@@ -0,0 +1,459 @@
+//! A two-dimensional grid of cells, the shape most puzzle inputs come in.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Position of a cell as `(row, column)`.
+pub type Position = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// A rectangular grid backed by a flat vector, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid by calling `f` with the position of every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut f)
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `(height, width)`, matching the order of positions.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.index_of(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `position` by `(d_row, d_col)`, returns [`None`] if it leaves the grid.
+    pub fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let position = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// Positions of the orthogonal neighbours that are inside the grid, clockwise from the top.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |offset| self.offset(position, *offset))
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours that are inside the grid, clockwise from the top left.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |offset| self.offset(position, *offset))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(
+            row < self.height,
+            "row {row} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics for a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.width,
+            "column {col} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Iterates over all cells along with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// Position of the first cell matching `predicate`, row by row.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.position_of(i))
+    }
+
+    /// Positions of all cells matching `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Applies `f` to every cell, keeping the dimensions.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn index_of(&self, (row, col): Position) -> usize {
+        row * self.width + col
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        (index / self.width, index % self.width)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirrors the grid along its main diagonal, i.e. rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, col)| {
+            self[(self.height - 1 - col, row)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, self.width - 1 - row)].clone()
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        assert!(
+            self.contains(position),
+            "position {position:?} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[self.index_of(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        assert!(
+            self.contains(position),
+            "position {position:?} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        let index = self.index_of(position);
+        &mut self.cells[index]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A row has a different length than the first row.
+    UnevenRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted into a cell.
+    InvalidCell { row: usize, col: usize, c: char },
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::UnevenRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+            ParseGridError::InvalidCell { row, col, c } => {
+                write!(f, "invalid cell {c:?} at row {row}, column {col}")
+            }
+        }
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    /// Parses one row per line and one cell per character, a final newline does not start another row.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (row, line) in s.lines().enumerate() {
+            let mut found = 0;
+            for (col, c) in line.chars().enumerate() {
+                let cell =
+                    T::try_from(c).map_err(|_| ParseGridError::InvalidCell { row, col, c })?;
+                cells.push(cell);
+                found += 1;
+            }
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseGridError::UnevenRow {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    /// Writes one line per row, the inverse of parsing for cells that display as a single character.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::point::Point2;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Tile {
+        Wall,
+        Open,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = ();
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Open),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn parses_cells() {
+        let grid: Grid<Tile> = "#.\n##".parse().unwrap();
+        assert_eq!(grid.dimensions(), (2, 2));
+        assert_eq!(grid[(0, 0)], Tile::Wall);
+        assert_eq!(grid.get((0, 1)), Some(&Tile::Open));
+        assert_eq!(grid.get((1, 0)), Some(&Tile::Wall));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get(Point2::new(1, 0).to_position()), Some(&Tile::Open));
+    }
+
+    #[test]
+    fn keeps_trailing_spaces() {
+        let grid: Grid<char> = "ab \n c \n".parse().unwrap();
+        assert_eq!(grid.dimensions(), (2, 3));
+        assert_eq!(grid.row(0), &['a', 'b', ' ']);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            "#.\n\n#.".parse::<Grid<Tile>>(),
+            Err(ParseGridError::UnevenRow {
+                row: 1,
+                expected: 2,
+                found: 0
+            })
+        );
+        assert_eq!(
+            "#.\n#".parse::<Grid<Tile>>(),
+            Err(ParseGridError::UnevenRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            "#.\n#x".parse::<Grid<Tile>>(),
+            Err(ParseGridError::InvalidCell {
+                row: 1,
+                col: 1,
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside of a 3x2 grid")]
+    fn rejects_rows_outside() {
+        grid().row(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of a 3x2 grid")]
+    fn rejects_columns_outside() {
+        grid().column(3);
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid: Grid<char> = "a.a\n.a.".parse().unwrap();
+        assert_eq!(grid.find(|c| *c == '.'), Some((0, 1)));
+        assert_eq!(
+            grid.find_all(|c| *c == 'a').collect::<Vec<_>>(),
+            vec![(0, 0), (0, 2), (1, 1)]
+        );
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+    }
+
+    #[test]
+    fn displays_as_text() {
+        let input = "abc\ndef\n";
+        assert_eq!(input.parse::<Grid<char>>().unwrap().to_string(), input);
+    }
+}
@@ -0,0 +1,350 @@
+//! Arithmetic on sets of integer intervals, e.g. for puzzles that map ranges of seeds.
+use std::ops::Range;
+
+use num::PrimInt;
+
+/// A half-open interval `[start, end)`, empty if `start >= end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval `[start, start + len)`.
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Values contained in both intervals, [`None`] if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let interval = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    /// Splits the interval into consecutive pieces at every boundary that lies inside of it.
+    pub fn split_at(&self, boundaries: impl IntoIterator<Item = T>) -> Vec<Self> {
+        let mut boundaries: Vec<T> = boundaries
+            .into_iter()
+            .filter(|b| self.start < *b && *b < self.end)
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut pieces = Vec::with_capacity(boundaries.len() + 1);
+        let mut start = self.start;
+        for boundary in boundaries {
+            pieces.push(Self::new(start, boundary));
+            start = boundary;
+        }
+        if start < self.end {
+            pieces.push(Self::new(start, self.end));
+        }
+        pieces
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of values stored as sorted, disjoint intervals.
+/// Overlapping and adjacent intervals are coalesced into one, so each set has a single representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Merges overlapping and adjacent intervals and drops empty ones.
+    pub fn coalesce(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_unstable();
+
+        let mut coalesced: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match coalesced.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => coalesced.push(interval),
+            }
+        }
+
+        Self {
+            intervals: coalesced,
+        }
+    }
+
+    /// The disjoint intervals of the set, in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, interval| acc + interval.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // NOTE: the intervals are sorted and disjoint, so at most the last interval starting before `value` can contain it.
+        let i = self.intervals.partition_point(|i| i.start <= value);
+        i > 0 && self.intervals[i - 1].contains(value)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|i| i.end - T::one())
+    }
+
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval.into());
+        *self = Self::coalesce(intervals);
+    }
+
+    /// Values contained in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::coalesce([self.intervals.as_slice(), &other.intervals].concat())
+    }
+
+    /// Values contained in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(interval) = a.intersection(b) {
+                intervals.push(interval);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::coalesce(intervals)
+    }
+
+    /// Values contained in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            for removed in other.iter().filter(|r| r.intersection(interval).is_some()) {
+                if start < removed.start {
+                    intervals.push(Interval::new(start, removed.start));
+                }
+                start = start.max(removed.end);
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+
+        Self::coalesce(intervals)
+    }
+
+    /// Splits every interval of the set at the given boundaries.
+    /// The pieces are not coalesced, which is why they are returned as a list of intervals.
+    pub fn split_at(&self, boundaries: &[T]) -> Vec<Interval<T>> {
+        self.intervals
+            .iter()
+            .flat_map(|interval| interval.split_at(boundaries.iter().copied()))
+            .collect()
+    }
+}
+
+impl<T: PrimInt, I: Into<Interval<T>>> FromIterator<I> for IntervalSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        Self::coalesce(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A function that shifts values in source intervals to a destination and leaves all other values unchanged.
+/// This is the shape of the "source range start, destination range start, length" maps of many puzzles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PiecewiseMap<T> {
+    /// Pairs of source interval and destination start, sorted by source.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: PrimInt> PiecewiseMap<T> {
+    pub fn new() -> Self {
+        Self { pieces: vec![] }
+    }
+
+    /// Maps `[source, source + len)` to `[destination, destination + len)`.
+    /// Values mapped by an earlier piece are not affected by overlapping later pieces.
+    pub fn insert(&mut self, destination: T, source: T, len: T) {
+        let source = Interval::with_len(source, len);
+        let covered: IntervalSet<T> = self.pieces.iter().map(|(s, _)| *s).collect();
+
+        // NOTE: only the uncovered parts of the source are added, which keeps the pieces disjoint.
+        for piece in IntervalSet::from_iter([source]).difference(&covered).iter() {
+            self.pieces
+                .push((*piece, destination + (piece.start - source.start)));
+        }
+
+        self.pieces.sort_unstable();
+    }
+
+    /// Maps a single value.
+    pub fn map_value(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    /// Maps every value of a set, splitting its intervals where pieces of the map begin and end.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let boundaries: Vec<T> = self
+            .pieces
+            .iter()
+            .flat_map(|(source, _)| [source.start, source.end])
+            .collect();
+
+        set.split_at(&boundaries)
+            .into_iter()
+            .map(|interval| {
+                let start = self.map_value(interval.start);
+                Interval::with_len(start, interval.len())
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalSet, PiecewiseMap};
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet<u64> {
+        intervals.iter().map(|(s, e)| *s..*e).collect()
+    }
+
+    #[test]
+    fn coalesces_intervals() {
+        let set = set(&[(5, 8), (0, 2), (2, 3), (6, 10), (12, 12)]);
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.min(), Some(0));
+        assert_eq!(set.max(), Some(9));
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert!(set.contains(9));
+    }
+
+    #[test]
+    fn computes_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+    }
+
+    #[test]
+    fn splits_at_boundaries() {
+        let interval = Interval::new(0_i32, 10);
+        assert_eq!(
+            interval.split_at([5, 3, 10, -1, 5]),
+            vec![
+                Interval::new(0, 3),
+                Interval::new(3, 5),
+                Interval::new(5, 10)
+            ]
+        );
+        assert_eq!(
+            set(&[(0, 4), (6, 8)]).split_at(&[2, 7]),
+            vec![
+                Interval::new(0, 2),
+                Interval::new(2, 4),
+                Interval::new(6, 7),
+                Interval::new(7, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_piecewise() {
+        // seed-to-soil map of 2023 day 5.
+        let mut map = PiecewiseMap::new();
+        map.insert(50_u64, 98, 2);
+        map.insert(52, 50, 48);
+
+        assert_eq!(map.map_value(79), 81);
+        assert_eq!(map.map_value(14), 14);
+        assert_eq!(map.map_value(99), 51);
+
+        let seeds = set(&[(79, 93), (55, 68)]);
+        assert_eq!(map.map_set(&seeds), set(&[(57, 70), (81, 95)]));
+
+        let around = set(&[(45, 101)]);
+        assert_eq!(map.map_set(&around), set(&[(45, 101)]));
+        assert_eq!(map.map_set(&around).len(), around.len());
+    }
+
+    #[test]
+    fn keeps_earlier_pieces_on_overlap() {
+        let mut map = PiecewiseMap::new();
+        map.insert(100_i64, 0, 10);
+        map.insert(200, 5, 10);
+        assert_eq!(map.map_value(7), 107);
+        assert_eq!(map.map_value(12), 207);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod grid;
+pub mod interval;
+pub mod parse;
+pub mod point;
 pub mod template;
 
 use std::num::ParseIntError;
@@ -1,4 +1,8 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, stars, time, verify, watch,
+};
+use advent_of_code::template::runner::Solution;
+use advent_of_code::template::Puzzle;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,74 +10,259 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Registry of all solutions in `src/bin`, generated by `build.rs`.
+mod solutions {
+    use advent_of_code::template::runner::Solution;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::{examples::parse_picks, solve::SolveOptions, time::HistoryOptions},
+        parse_mebibytes, parse_param, parse_secs, BenchConfig, Day, Limits, MultiRunOptions, Year,
+        HEAP_STATS_ENABLED,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            picks: Option<Vec<usize>>,
+            force: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
+            force: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            options: SolveOptions,
         },
         All {
-            release: bool,
+            record: bool,
+            options: MultiRunOptions<'static>,
         },
+        Verify {
+            options: MultiRunOptions<'static>,
+        },
+        Stars,
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            history: HistoryOptions,
+            options: MultiRunOptions<'static>,
+        },
+        Watch {
+            day: Day,
+            options: MultiRunOptions<'static>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            template: Option<String>,
+        },
+    }
+
+    /// Reads benchmark settings from the environment, overridden by command-line flags.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let mut config = BenchConfig::from_env()?;
+
+        if let Some(x) = args.opt_value_from_fn("--bench-time", parse_secs)? {
+            config.bench_time = x;
+        }
+        if let Some(x) = args.opt_value_from_fn("--warmup", parse_secs)? {
+            config.warmup = x;
+        }
+        if let Some(x) = args.opt_value_from_str("--min-samples")? {
+            config.min_samples = x;
+        }
+        if let Some(x) = args.opt_value_from_str("--max-samples")? {
+            config.max_samples = x;
+        }
+
+        Ok(config.validate()?)
+    }
+
+    /// Reads the limits of spawned solutions from the environment, overridden by command-line flags.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let mut limits = Limits::from_env()?;
+
+        if let Some(x) = args.opt_value_from_fn("--timeout", parse_secs)? {
+            limits.timeout = Some(x);
+        }
+        if let Some(x) = args.opt_value_from_fn("--memory-limit", parse_mebibytes)? {
+            limits.memory = Some(x);
+        }
+
+        Ok(limits.validate()?)
+    }
+
+    /// Reads the options for comparing benchmarks with previous runs.
+    fn parse_history_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<HistoryOptions, Box<dyn std::error::Error>> {
+        let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+        if !threshold.is_finite() || threshold < 0.0 {
+            return Err("`--threshold` must be a non-negative percentage.".into());
+        }
+
+        Ok(HistoryOptions {
+            label: args.opt_value_from_str("--label")?,
+            compare: args.contains("--compare"),
+            baseline: args.opt_value_from_str("--baseline")?,
+            threshold,
+        })
+    }
+
+    /// Reads the options shared by the commands that run several days.
+    /// Solutions run with the profile of this binary, so they run in-process unless `--release` asks for another one.
+    fn parse_run_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<MultiRunOptions<'static>, Box<dyn std::error::Error>> {
+        let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+        // NOTE: allocations are counted process-wide, concurrent days would mix up their counts.
+        if HEAP_STATS_ENABLED && jobs > 1 {
+            return Err(
+                "`--jobs` is not supported by builds with the `heap-stats` feature.".into(),
+            );
+        }
+
+        Ok(MultiRunOptions {
+            is_release: args.contains("--release") || cfg!(not(debug_assertions)),
+            bench_config: None,
+            jobs,
+            heap_stats: false,
+            limits: parse_limits(args)?,
+            solutions: super::solutions(args.contains("--subprocess")),
+        })
+    }
+
+    /// Reads the year from the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "no year specified, pass `--year` or set `AOC_YEAR`.".into()),
+        }
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let subcommand = args.subcommand()?;
+        let year = parse_year(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let record = args.contains("--record");
+                let heap_stats = args.contains("--heap");
+                let options = parse_run_options(&mut args)?;
+
+                if heap_stats && options.jobs > 1 {
+                    return Err("`--heap` cannot be combined with `--jobs`.".into());
+                }
+
+                AppArguments::All {
+                    record,
+                    options: MultiRunOptions {
+                        heap_stats,
+                        ..options
+                    },
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                options: parse_run_options(&mut args)?,
             },
+            Some("stars") => AppArguments::Stars,
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let history = parse_history_options(&mut args)?;
+
+                // NOTE: benchmarks always run serially on release builds.
+                let options = MultiRunOptions {
+                    is_release: true,
+                    bench_config: Some(parse_bench_config(&mut args)?),
+                    jobs: 1,
+                    heap_stats: args.contains("--heap"),
+                    limits: parse_limits(&mut args)?,
+                    solutions: super::solutions(args.contains("--subprocess")),
+                };
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    history,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("examples") => AppArguments::Examples {
+                picks: args.opt_value_from_fn("--pick", parse_picks)?,
+                force: args.contains("--force"),
+                day: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
+                force: args.contains("--force"),
+                template: args.opt_value_from_str("--template")?,
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let time = args.contains("--time");
+                let bench_config = parse_bench_config(&mut args)?;
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    options: SolveOptions {
+                        release: args.contains("--release"),
+                        submit: args.opt_value_from_str("--submit")?,
+                        record: args.contains("--record"),
+                        dhat: args.contains("--dhat"),
+                        heap_stats: args.contains("--heap"),
+                        examples: args.contains("--examples"),
+                        params: args
+                            .values_from_fn("--param", parse_param)?
+                            .into_iter()
+                            .collect(),
+                        bench_config: time.then_some(bench_config),
+                    },
+                }
+            }
+            Some("watch") => AppArguments::Watch {
+                options: MultiRunOptions {
+                    is_release: args.contains("--release"),
+                    bench_config: None,
+                    jobs: 1,
+                    heap_stats: false,
+                    limits: parse_limits(&mut args)?,
+                    solutions: &[],
+                },
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                template: args.opt_value_from_str("--template")?,
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -89,7 +278,23 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+}
+
+/// Solutions that can be run in-process, empty if `--subprocess` is passed.
+fn solutions(subprocess: bool) -> &'static [Solution] {
+    if subprocess {
+        &[]
+    } else {
+        solutions::SOLUTIONS
+    }
+}
+
+/// Downloads a puzzle before scaffolding it. A failed download only prints a warning, the day is scaffolded anyway.
+fn download_before_scaffold(puzzle: Puzzle, force: bool) {
+    if let Err(e) = download::download(puzzle, force) {
+        eprintln!("Warning: failed to download puzzle {puzzle}, scaffolding without it: {e}");
     }
 }
 
@@ -99,30 +304,48 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+        Ok((year, args)) => match args {
+            AppArguments::All { record, options } => all::handle(year, record, &options),
+            AppArguments::Verify { options } => verify::handle(year, &options),
+            AppArguments::Stars => stars::handle(year),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                history,
+                options,
+            } => time::handle(year, day, all, store, &history, &options),
+            AppArguments::Download { day, force } => {
+                download::handle(Puzzle::new(year, day), force);
+            }
+            AppArguments::Read { day } => read::handle(Puzzle::new(year, day), true),
+            AppArguments::Examples { day, picks, force } => {
+                examples::handle(Puzzle::new(year, day), picks, force);
+            }
+            AppArguments::Scaffold {
+                day,
+                download,
+                force,
+                template,
+            } => {
+                let puzzle = Puzzle::new(year, day);
+                // NOTE: the description is downloaded first, so templates can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download_before_scaffold(puzzle, force);
                 }
+                scaffold::handle(puzzle, template.as_deref());
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            AppArguments::Solve { day, options } => solve::handle(Puzzle::new(year, day), &options),
+            AppArguments::Watch { day, options } => watch::handle(Puzzle::new(year, day), &options),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { template } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::new(year, day);
+                        download_before_scaffold(puzzle, false);
+                        scaffold::handle(puzzle, template.as_deref());
+                        // NOTE: the description was just downloaded, part two is not unlocked yet.
+                        read::handle(puzzle, false)
                     }
                     None => {
                         eprintln!(
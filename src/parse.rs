@@ -0,0 +1,243 @@
+//! Helpers for the shapes puzzle inputs usually come in.
+use std::{fmt::Display, str::FromStr};
+
+/// An error which can be returned by the parsing helpers, pointing at the offending part of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, starting at 1.
+    pub line: usize,
+    /// Column of the line in characters, starting at 1.
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error of a single line to line `line` of the input.
+    pub fn at_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Parses `s` into `T`, reporting failures at `column` of line 1.
+fn parse_at<T>(s: &str, column: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.parse()
+        .map_err(|e| ParseError::new(1, column, format!("failed to parse {s:?}: {e}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all integers of a line, e.g. `[-2, 18, 15]` from `Sensor at x=-2, y=18: 15`.
+/// A `-` directly in front of a number is its sign, unless it follows a digit as in `1-3`.
+/// Numbers that do not fit into `T` are reported with their column.
+pub fn integers<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let chars: Vec<char> = line.chars().collect();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let is_sign = chars[i] == '-'
+            && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+            && !(i > 0 && chars[i - 1].is_ascii_digit());
+
+        if !is_sign && !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let token: String = chars[start..i].iter().collect();
+        numbers.push(parse_at(&token, start + 1)?);
+    }
+
+    Ok(numbers)
+}
+
+/// Splits the input into sections separated by blank lines, trimming surrounding newlines.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&input[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        sections.push(&input[s..end]);
+    }
+
+    sections
+}
+
+/// Splits a `key: value` line into its trimmed parts.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(1, 1, format!("expected `key: value`, got {line:?}")))?;
+    Ok((key.trim(), value.trim()))
+}
+
+/// Splits a `A = (B, C)` line into `("A", ("B", "C"))`.
+pub fn node(line: &str) -> Result<(&str, (&str, &str)), ParseError> {
+    let error = |column: usize, expected: &str| {
+        ParseError::new(
+            1,
+            column,
+            format!("expected {expected} in `A = (B, C)`, got {line:?}"),
+        )
+    };
+    // NOTE: every part is a subslice of `line`, so its column follows from its address.
+    let column = |part: &str| {
+        let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+        line[..offset].chars().count() + 1
+    };
+
+    let (name, rest) = line.split_once('=').ok_or_else(|| error(1, "`=`"))?;
+    let inner = rest.trim();
+    let inner = inner
+        .strip_prefix('(')
+        .ok_or_else(|| error(column(rest.trim_start()), "`(`"))?;
+    let inner = inner
+        .strip_suffix(')')
+        .ok_or_else(|| error(column(&line[line.len()..]), "`)`"))?;
+    let (left, right) = inner
+        .split_once(',')
+        .ok_or_else(|| error(column(inner), "`,`"))?;
+
+    Ok((name.trim(), (left.trim(), right.trim())))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses every non-empty line into `T` through [`FromStr`], reporting the line that failed.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_lines_with(input, |line| parse_at(line, 1))
+}
+
+/// Parses every non-empty line with `f`, moving its errors to the line that failed.
+pub fn parse_lines_with<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| f(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{integers, key_value, node, parse_lines, parse_lines_with, sections, ParseError};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            integers::<i64>("Sensor at x=-2, y=18: closest beacon is at x=-2, y=15"),
+            Ok(vec![-2, 18, -2, 15])
+        );
+        assert_eq!(integers::<u8>("1-3 a: 12"), Ok(vec![1, 3, 12]));
+        assert_eq!(integers::<i128>("-"), Ok(vec![]));
+        assert_eq!(
+            integers::<i128>("170141183460469231731687303715884105727"),
+            Ok(vec![i128::MAX])
+        );
+    }
+
+    #[test]
+    fn reports_integer_columns() {
+        let error = integers::<u8>("Game 1: 256 red").unwrap_err();
+        assert_eq!(error.column, 9);
+        assert!(error.message.contains("\"256\""));
+
+        let error = integers::<u32>("x=-1").unwrap_err();
+        assert_eq!(error.column, 3);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\nseeds: 1 2\n\r\nseed-to-soil map:\n50 98 2\n  \n\nlast\n";
+        assert_eq!(
+            sections(input),
+            vec!["seeds: 1 2", "seed-to-soil map:\n50 98 2", "last"]
+        );
+        assert_eq!(sections("a\r\nb\r\n\r\nc"), vec!["a\r\nb", "c"]);
+        assert!(sections("\n\n").is_empty());
+    }
+
+    #[test]
+    fn parses_shapes() {
+        assert_eq!(
+            key_value("Card 1: 41 48 | 83 86"),
+            Ok(("Card 1", "41 48 | 83 86"))
+        );
+        assert!(key_value("Card 1").is_err());
+        assert_eq!(node("AAA = (BBB, CCC)"), Ok(("AAA", ("BBB", "CCC"))));
+        assert_eq!(node("AAA = BBB, CCC)").unwrap_err().column, 7);
+        assert_eq!(node("AAA = (BBB CCC)").unwrap_err().column, 8);
+        assert_eq!(node("AAA = (BBB, CCC").unwrap_err().column, 16);
+    }
+
+    #[test]
+    fn reports_failing_lines() {
+        assert_eq!(parse_lines::<i32>("1\n\n-2\n"), Ok(vec![1, -2]));
+
+        let error = parse_lines::<u32>("1\n2\nthree").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 1);
+
+        let error = parse_lines_with("1 2\n3 x=1000", integers::<u8>).unwrap_err();
+        assert_eq!(error, ParseError::new(2, 5, error.message.clone()));
+        assert!(error.to_string().starts_with("line 2, column 5: "));
+    }
+}
@@ -0,0 +1,320 @@
+//! Points, directions and distance metrics for puzzles on grids and in space.
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::{Signed, Zero};
+
+use crate::grid::Position;
+
+/// Absolute difference of two values, also for unsigned types.
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point in two dimensions. On grids, `x` is the column and `y` is the row, growing downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Zero> Point2<T> {
+    pub fn origin() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// Sum of the distances along each axis.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Largest distance along any axis, i.e. the number of king moves between two points.
+    pub fn chebyshev(self, other: Self) -> T {
+        max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + Signed> Point2<T> {
+    /// Moves one step into `direction`.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+}
+
+impl Point2<usize> {
+    /// Moves the point by `delta`, returns [`None`] if it leaves `[0, width) x [0, height)`.
+    pub fn checked_offset(self, delta: Point2<isize>, width: usize, height: usize) -> Option<Self> {
+        let x = self.x.checked_add_signed(delta.x)?;
+        let y = self.y.checked_add_signed(delta.y)?;
+        (x < width && y < height).then_some(Self { x, y })
+    }
+
+    /// Moves one step into `direction`, returns [`None`] if it leaves `[0, width) x [0, height)`.
+    pub fn checked_step(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        self.checked_offset(direction.delta(), width, height)
+    }
+
+    /// Orthogonal neighbours inside `[0, width) x [0, height)`, in the order of [`Direction::ALL`].
+    pub fn neighbours4(self, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.checked_step(direction, width, height))
+    }
+
+    /// Converts a grid [`Position`] `(row, column)` into a point, swapping the coordinates.
+    pub const fn from_position((row, col): Position) -> Self {
+        Self::new(col, row)
+    }
+
+    /// Converts the point into a grid [`Position`] `(row, column)`, swapping the coordinates.
+    pub const fn to_position(self) -> Position {
+        (self.y, self.x)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point in three dimensions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// Sum of the distances along each axis.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Largest distance along any axis.
+    pub fn chebyshev(self, other: Self) -> T {
+        max(
+            max(abs_diff(self.x, other.x), abs_diff(self.y, other.y)),
+            abs_diff(self.z, other.z),
+        )
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal directions on a grid, `North` pointing to the first row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from `North`.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Direction after turning 90 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    /// Direction after turning 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Offset of a single step into this direction.
+    pub fn delta<T: Signed>(self) -> Point2<T> {
+        match self {
+            Direction::North => Point2::new(T::zero(), -T::one()),
+            Direction::East => Point2::new(T::one(), T::zero()),
+            Direction::South => Point2::new(T::zero(), T::one()),
+            Direction::West => Point2::new(-T::one(), T::zero()),
+        }
+    }
+
+    /// Offset of a single step as `(row, column)`, as used by [`Grid::offset`](crate::grid::Grid::offset).
+    pub fn grid_delta(self) -> (isize, isize) {
+        let delta = self.delta::<isize>();
+        (delta.y, delta.x)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Point2, Point3};
+
+    #[test]
+    fn computes_distances() {
+        let a = Point2::new(1_usize, 6);
+        let b = Point2::new(5_usize, 11);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(b.manhattan(a), 9);
+        assert_eq!(a.chebyshev(b), 5);
+
+        let a = Point3::new(-1_i64, 2, 3);
+        let b = Point3::new(2_i64, -2, 3);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(
+                direction.delta::<i32>() + direction.opposite().delta(),
+                Point2::origin()
+            );
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+    }
+
+    #[test]
+    fn steps_signed_points() {
+        let point = Point2::new(0_i64, 0);
+        assert_eq!(point.step(Direction::North), Point2::new(0, -1));
+        assert_eq!(point.step(Direction::West), Point2::new(-1, 0));
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let point = Point2::new(0_usize, 1);
+        assert_eq!(point.checked_step(Direction::West, 3, 2), None);
+        assert_eq!(point.checked_step(Direction::South, 3, 2), None);
+        assert_eq!(
+            point.checked_step(Direction::North, 3, 2),
+            Some(Point2::new(0, 0))
+        );
+        assert_eq!(
+            point.neighbours4(3, 2).collect::<Vec<_>>(),
+            vec![Point2::new(0, 0), Point2::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let point = Point2::from_position((1, 2));
+        assert_eq!(point, Point2::new(2, 1));
+        assert_eq!(point.to_position(), (1, 2));
+        assert_eq!(Direction::North.grid_delta(), (-1, 0));
+    }
+}
@@ -0,0 +1,366 @@
+use std::{collections::HashMap, fs, io::Error, process, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::output::outln;
+use crate::template::protocol::PartRecord;
+use crate::template::submissions::Submissions;
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// The result of comparing an answer with the ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the recorded answer.
+    Match,
+    /// The answer differs from the recorded answer.
+    Mismatch { expected: String },
+    /// There is no recorded answer for this part.
+    Unrecorded,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(year.data_dir().join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Year) -> Self {
+        let path = year.data_dir().join(ANSWERS_FILE_NAME);
+        let Ok(s) = fs::read_to_string(&path) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to read \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Returns the recorded answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&String> {
+        self.data
+            .iter()
+            .find(|a| a.day == day)
+            .and_then(|a| a.get(part))
+    }
+
+    /// Record an accepted answer, overwriting a previously recorded answer for the same part.
+    /// Returns the previous answer if it differs from the new one.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str) -> Result<Option<String>, String> {
+        if !matches!(part, 1 | 2) {
+            return Err(format!("invalid part {part}, expected 1 or 2."));
+        }
+
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = match part {
+            1 => &mut self.data[index].part_1,
+            2 => &mut self.data[index].part_2,
+            _ => unreachable!(),
+        };
+
+        Ok(entry
+            .replace(answer.to_string())
+            .filter(|previous| previous != answer))
+    }
+
+    /// Compare an answer with the ledger. A missing answer never matches a recorded one.
+    pub fn verify(&self, day: Day, part: u8, answer: Option<&str>) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Unrecorded,
+            Some(expected) if Some(expected.as_str()) == answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// Record the answers of `records` in the ledger file, reporting answers that changed.
+/// Answers that the submission history does not mark as correct are recorded too, with a warning.
+pub fn record_answers(year: Year, records: &[PartRecord]) {
+    let mut answers = Answers::read_from_file(year);
+    let submissions = Submissions::read_from_file(year);
+    let mut recorded = 0;
+    let mut unconfirmed = 0;
+
+    for record in records {
+        let Some(answer) = &record.answer else {
+            continue;
+        };
+        match answers.record(record.day, record.part, answer) {
+            Ok(Some(previous)) => outln!(
+                "Day {} Part {}: replaced recorded answer `{previous}` with `{answer}`.",
+                record.day,
+                record.part
+            ),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Failed to record answer of day {}: {e}", record.day);
+                continue;
+            }
+        }
+        if !submissions.is_correct(record.day, record.part, answer) {
+            unconfirmed += 1;
+        }
+        recorded += 1;
+    }
+
+    if unconfirmed > 0 {
+        outln!("Warning: {unconfirmed} recorded answer(s) not confirmed by a submission.");
+    }
+
+    match answers.store_file(year) {
+        Ok(()) => outln!(
+            "Recorded {recorded} answer(s) in \"{}\".",
+            year.data_dir().join(ANSWERS_FILE_NAME).display()
+        ),
+        Err(e) => eprintln!("Failed to store answers: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answer, Answers};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("142".into()),
+                    part_2: Some("281".into()),
+                },
+                Answer {
+                    day: day!(4),
+                    part_1: Some("13".into()),
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    mod deserialization {
+        use crate::{day, template::answers::Answers};
+
+        #[test]
+        fn handles_json_answers() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part_1": "142", "part_2": null }] }"#.to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            let answer = answers.data.first().unwrap();
+            assert_eq!(answer.day, day!(1));
+            assert_eq!(answer.part_1, Some("142".to_string()));
+            assert_eq!(answer.part_2, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_answers() {
+            let json = r#"{ "data": [{ "day": "26", "part_1": 1 }] }"#.to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod record {
+        use super::get_mock_answers;
+        use crate::day;
+
+        #[test]
+        fn records_new_days_in_order() {
+            let mut answers = get_mock_answers();
+            assert_eq!(answers.record(day!(2), 2, "8"), Ok(None));
+            assert_eq!(answers.data.len(), 3);
+            assert_eq!(answers.data[1].day, day!(2));
+            assert_eq!(answers.data[1].part_1, None);
+            assert_eq!(answers.data[1].part_2, Some("8".into()));
+        }
+
+        #[test]
+        fn returns_changed_answers() {
+            let mut answers = get_mock_answers();
+            assert_eq!(answers.record(day!(1), 1, "142"), Ok(None));
+            assert_eq!(answers.record(day!(1), 1, "143"), Ok(Some("142".into())));
+            assert_eq!(answers.get(day!(1), 1), Some(&"143".into()));
+        }
+
+        #[test]
+        fn rejects_invalid_parts() {
+            let mut answers = get_mock_answers();
+            assert!(answers.record(day!(1), 3, "1").is_err());
+            assert_eq!(answers.get(day!(1), 1), Some(&"142".into()));
+        }
+    }
+
+    mod verify {
+        use super::get_mock_answers;
+        use crate::{day, template::answers::Verdict};
+
+        #[test]
+        fn verifies_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(answers.verify(day!(1), 1, Some("142")), Verdict::Match);
+            assert_eq!(
+                answers.verify(day!(1), 2, Some("280")),
+                Verdict::Mismatch {
+                    expected: "281".into()
+                }
+            );
+            assert_eq!(
+                answers.verify(day!(4), 1, None),
+                Verdict::Mismatch {
+                    expected: "13".into()
+                }
+            );
+            assert_eq!(answers.verify(day!(4), 2, Some("30")), Verdict::Unrecorded);
+            assert_eq!(answers.verify(day!(5), 1, Some("35")), Verdict::Unrecorded);
+        }
+    }
+}
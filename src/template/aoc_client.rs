@@ -0,0 +1,416 @@
+//! Client for the Advent of Code website, used to read puzzles, download inputs and submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::template::Puzzle;
+
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const SESSION_ENV: &str = "AOC_SESSION";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = "adventofcode.session";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    /// The puzzle unlocks in the given number of seconds.
+    Locked(u64),
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set {SESSION_ENV} or write it to \"~/.{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Locked(secs) => write!(
+                f,
+                "the puzzle is not unlocked yet, it unlocks in {}h {}m {}s.",
+                secs / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            ),
+            AocClientError::BadStatus(404) => write!(
+                f,
+                "adventofcode.com responded with 404, the puzzle might not be unlocked yet."
+            ),
+            AocClientError::BadStatus(400) => write!(
+                f,
+                "adventofcode.com responded with 400, the session cookie might be expired."
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach adventofcode.com: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Talks to the Advent of Code endpoints.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the environment.
+    ///
+    /// The session cookie is read from `AOC_SESSION`, falling back to `~/.adventofcode.session` and
+    /// `$XDG_CONFIG_HOME/adventofcode.session` (the locations used by aoc-cli).
+    /// `AOC_BASE_URL` overrides the address of the website, e.g. to use a local mock server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = env::var(SESSION_ENV)
+            .ok()
+            .or_else(read_session_file)
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(AocClientError::SessionNotFound)?;
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Returns an error if the puzzle is not unlocked at `now` (seconds since the unix epoch).
+    pub fn check_unlocked(&self, puzzle: Puzzle, now: u64) -> Result<(), AocClientError> {
+        let unlock = puzzle.day.unlock_timestamp(puzzle.year.into_inner());
+        let now = i64::try_from(now).unwrap_or(i64::MAX);
+
+        if now < unlock {
+            Err(AocClientError::Locked(unlock.abs_diff(now)))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fetches the input of a puzzle.
+    pub fn get_input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        let response = self.request("GET", &url).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the description of a puzzle, converted to markdown.
+    pub fn get_puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let response = self.request("GET", &self.puzzle_url(puzzle)).call()?;
+        Ok(html_to_markdown(&response.into_string()?))
+    }
+
+    /// Submits an answer, returns the response of the website as text.
+    pub fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        Ok(html_to_markdown(&response.into_string()?))
+    }
+
+    fn puzzle_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok();
+
+    let candidates = [
+        home.map(|x| PathBuf::from(x).join(format!(".{SESSION_FILE_NAME}"))),
+        env::var("XDG_CONFIG_HOME")
+            .ok()
+            .map(|x| PathBuf::from(x).join(SESSION_FILE_NAME)),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find_map(|path| fs::read_to_string(path).ok())
+}
+
+pub fn get_input_path(puzzle: Puzzle) -> PathBuf {
+    puzzle.data_path("inputs", "", "txt")
+}
+
+pub fn get_puzzle_path(puzzle: Puzzle) -> PathBuf {
+    puzzle.data_path("puzzles", "", "md")
+}
+
+/// Returns `true` if a downloaded description includes part two, which is only shown once part one is solved.
+pub fn has_part_two(description: &str) -> bool {
+    description
+        .lines()
+        .any(|line| line.starts_with("## --- Part Two"))
+}
+
+/// Writes `contents` to `path`, creating parent directories if needed.
+pub fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Converts the `<article>` elements of a page to markdown.
+/// Only covers the handful of tags used in puzzle descriptions; code blocks become fenced blocks and
+/// emphasized text (e.g. example answers) becomes `*text*`.
+fn html_to_markdown(html: &str) -> String {
+    let article_re = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let tag_re = Regex::new(r"<(/?)([a-zA-Z0-9]+)[^>]*>").unwrap();
+
+    let articles: Vec<&str> = article_re
+        .captures_iter(html)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .collect();
+
+    // responses to submissions wrap their text in a single article, other pages might not have one at all.
+    let sources = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    let mut out = String::new();
+
+    for source in sources {
+        let mut in_pre = false;
+        let mut last = 0;
+
+        for captures in tag_re.captures_iter(source) {
+            let tag = captures.get(0).unwrap();
+            push_text(&mut out, &source[last..tag.start()], in_pre);
+            last = tag.end();
+
+            let is_close = &captures[1] == "/";
+
+            match (captures[2].to_lowercase().as_str(), is_close) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                ("code", _) if !in_pre => out.push('`'),
+                ("em", _) if !in_pre => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) => out.push('\n'),
+                ("br", _) => out.push('\n'),
+                _ => {}
+            }
+        }
+
+        push_text(&mut out, &source[last..], in_pre);
+        if !out.ends_with("\n\n") {
+            out.push_str("\n\n");
+        }
+    }
+
+    out.trim().to_string() + "\n"
+}
+
+/// Line breaks in the html source are only significant in code blocks.
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        let text = text.replace('\n', " ");
+        if out.is_empty() || out.ends_with('\n') {
+            out.push_str(text.trim_start());
+        } else {
+            out.push_str(&text);
+        }
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{has_part_two, html_to_markdown, AocClient};
+    use crate::{day, template::Puzzle};
+
+    fn puzzle(day: crate::template::Day) -> Puzzle {
+        Puzzle::new("2023".parse().unwrap(), day)
+    }
+
+    /// Serves a single request with `body`, returns the base url and a handle to the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (base_url, handle) = mock_server(200, "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&base_url, "secret\n");
+
+        let input = client.get_input(puzzle(day!(1))).unwrap();
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let response = client.submit(puzzle(day!(5)), 2, "46").unwrap();
+        assert_eq!(
+            response,
+            "That's not the right answer; your answer is too high.\n"
+        );
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=46"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, handle) = mock_server(404, "not found");
+        let client = AocClient::new(&base_url, "secret");
+
+        let err = client.get_puzzle(puzzle(day!(25))).unwrap_err();
+        assert!(matches!(err, super::AocClientError::BadStatus(404)));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn refuses_locked_puzzles() {
+        let client = AocClient::new("http://localhost", "secret");
+        let unlock = day!(2).unlock_timestamp(2023) as u64;
+
+        assert!(matches!(
+            client.check_unlocked(puzzle(day!(2)), unlock - 90),
+            Err(super::AocClientError::Locked(90))
+        ));
+        assert!(client.check_unlocked(puzzle(day!(2)), unlock).is_ok());
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+<em>pqr3</em>stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code> &amp; <a href="/x">more</a>.</p>
+</article><p>Answer: <form></form></p></main>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Trebuchet?! ---\n\nFor example:\n\n```\n1abc2\npqr3stu8vwx\n```\n\nAdding these together produces `*142*` & more.\n"
+        );
+
+        assert!(!has_part_two(&html_to_markdown(html)));
+        assert!(has_part_two("## --- Day 1 ---\n\n## --- Part Two ---\n"));
+    }
+}
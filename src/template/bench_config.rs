@@ -0,0 +1,183 @@
+//! Settings that control how many samples are collected when benching a solution part.
+use std::{env, time::Duration};
+
+const BENCH_TIME_ARG: &str = "--bench-time";
+const MIN_SAMPLES_ARG: &str = "--min-samples";
+const MAX_SAMPLES_ARG: &str = "--max-samples";
+const WARMUP_ARG: &str = "--warmup";
+
+const BENCH_TIME_ENV: &str = "AOC_BENCH_TIME";
+const MIN_SAMPLES_ENV: &str = "AOC_MIN_SAMPLES";
+const MAX_SAMPLES_ENV: &str = "AOC_MAX_SAMPLES";
+const WARMUP_ENV: &str = "AOC_WARMUP";
+
+/// The budget of a benchmark run.
+///
+/// The runner first executes a solution part for the `warmup` duration, then collects as many samples
+/// as fit into `bench_time`, bounded by `min_samples` and `max_samples`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub bench_time: Duration,
+    pub warmup: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            bench_time: Duration::from_secs(1),
+            warmup: Duration::from_millis(100),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads the config from the `AOC_BENCH_TIME`, `AOC_WARMUP`, `AOC_MIN_SAMPLES` and `AOC_MAX_SAMPLES`
+    /// environment variables, falling back to the defaults for unset values.
+    /// The result is not validated as it is usually overridden by command-line flags, see [`BenchConfig::validate`].
+    pub fn from_env() -> Result<Self, String> {
+        let mut config = Self::default();
+
+        if let Ok(x) = env::var(BENCH_TIME_ENV) {
+            config.bench_time = parse_secs(&x)?;
+        }
+        if let Ok(x) = env::var(WARMUP_ENV) {
+            config.warmup = parse_secs(&x)?;
+        }
+        if let Ok(x) = env::var(MIN_SAMPLES_ENV) {
+            config.min_samples = parse_samples(&x)?;
+        }
+        if let Ok(x) = env::var(MAX_SAMPLES_ENV) {
+            config.max_samples = parse_samples(&x)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the config from command-line arguments as forwarded by [`BenchConfig::to_args`].
+    /// Values that are not passed are read from the environment.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::from_env()?;
+
+        let value_of = |arg: &str| {
+            args.iter()
+                .position(|x| x == arg)
+                .map(|i| args.get(i + 1).ok_or(format!("missing value for {arg}.")))
+                .transpose()
+        };
+
+        if let Some(x) = value_of(BENCH_TIME_ARG)? {
+            config.bench_time = parse_secs(x)?;
+        }
+        if let Some(x) = value_of(WARMUP_ARG)? {
+            config.warmup = parse_secs(x)?;
+        }
+        if let Some(x) = value_of(MIN_SAMPLES_ARG)? {
+            config.min_samples = parse_samples(x)?;
+        }
+        if let Some(x) = value_of(MAX_SAMPLES_ARG)? {
+            config.max_samples = parse_samples(x)?;
+        }
+
+        config.validate()
+    }
+
+    /// Converts the config to command-line arguments that can be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            BENCH_TIME_ARG.into(),
+            self.bench_time.as_secs_f64().to_string(),
+            WARMUP_ARG.into(),
+            self.warmup.as_secs_f64().to_string(),
+            MIN_SAMPLES_ARG.into(),
+            self.min_samples.to_string(),
+            MAX_SAMPLES_ARG.into(),
+            self.max_samples.to_string(),
+        ]
+    }
+
+    /// Returns the config if it is valid, an error otherwise.
+    pub fn validate(self) -> Result<Self, String> {
+        if self.min_samples == 0 {
+            return Err("expecting at least one sample.".into());
+        }
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "min samples ({}) can not be larger than max samples ({}).",
+                self.min_samples, self.max_samples
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Number of samples to collect for a part that takes `time_per_run` to execute.
+    pub fn iterations(&self, time_per_run: &Duration) -> u128 {
+        (self.bench_time.as_nanos() / time_per_run.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+/// Parses a (fractional) number of seconds, e.g. `2` or `0.25`.
+pub fn parse_secs(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+        .ok_or(format!(
+            "expecting a positive number of seconds, got `{s}`."
+        ))
+}
+
+fn parse_samples(s: &str) -> Result<u128, String> {
+    s.parse::<u128>()
+        .map_err(|_| format!("expecting a positive number of samples, got `{s}`."))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_args() {
+        let config = BenchConfig {
+            bench_time: Duration::from_millis(2500),
+            warmup: Duration::ZERO,
+            min_samples: 5,
+            max_samples: 50,
+        };
+        assert_eq!(BenchConfig::from_args(&config.to_args()).unwrap(), config);
+    }
+
+    #[test]
+    fn ignores_unrelated_args() {
+        let args = vec!["--time".into(), "--max-samples".into(), "20".into()];
+        let config = BenchConfig::from_args(&args).unwrap();
+        assert_eq!(config.max_samples, 20);
+        assert_eq!(config.bench_time, Duration::from_secs(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_sample_range() {
+        let args = vec![
+            "--min-samples".into(),
+            "20".into(),
+            "--max-samples".into(),
+            "10".into(),
+        ];
+        BenchConfig::from_args(&args).unwrap();
+    }
+
+    #[test]
+    fn computes_iterations() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations(&Duration::from_millis(10)), 100);
+        assert_eq!(config.iterations(&Duration::from_secs(5)), 10);
+        assert_eq!(config.iterations(&Duration::from_nanos(1)), 10000);
+    }
+}
@@ -1,5 +1,21 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    answers::record_answers,
+    run_multi::{run_multi, MultiRunOptions},
+    Year,
+};
+
+pub fn handle(year: Year, record: bool, options: &MultiRunOptions) {
+    let run = run_multi(year, &all_days().collect(), options);
+
+    if record {
+        println!();
+        record_answers(year, &run.records);
+    }
+
+    if run.has_failures() {
+        process::exit(1);
+    }
 }
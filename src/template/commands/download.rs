@@ -1,14 +1,60 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::aoc_client::{
+    get_input_path, get_puzzle_path, has_part_two, write_file, AocClient, AocClientError,
+};
+use crate::template::fetch_log::FetchLog;
+use crate::template::submissions::now;
+use crate::template::Puzzle;
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: Puzzle, force: bool) {
+    if let Err(e) = download(puzzle, force) {
+        eprintln!("failed to download puzzle {puzzle}: {e}");
         process::exit(1);
+    };
+}
+
+/// Downloads input and description of a puzzle. Unless `force` is set, an input that already exists and is not empty
+/// is skipped, as is a description that already includes part two.
+pub fn download(puzzle: Puzzle, force: bool) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    client.check_unlocked(puzzle, now())?;
+
+    let mut log = FetchLog::read_from_file();
+
+    let input_path = get_input_path(puzzle);
+    if force || is_missing(&input_path) {
+        let input = log.throttled(puzzle, "input", || client.get_input(puzzle))?;
+        write_file(&input_path, &input)?;
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+    } else {
+        println!(
+            "Skipped input, \"{}\" already exists.",
+            input_path.display()
+        );
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let puzzle_path = get_puzzle_path(puzzle);
+    let is_complete = fs::read_to_string(&puzzle_path).is_ok_and(|d| has_part_two(&d));
+    if force || !is_complete {
+        let description = log.throttled(puzzle, "puzzle", || client.get_puzzle(puzzle))?;
+        write_file(&puzzle_path, &description)?;
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+    } else {
+        println!(
+            "Skipped puzzle, \"{}\" already includes part two.",
+            puzzle_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn is_missing(path: &Path) -> bool {
+    fs::metadata(path).map_or(true, |m| m.len() == 0)
 }
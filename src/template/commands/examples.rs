@@ -0,0 +1,265 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{
+    aoc_client::{get_puzzle_path, write_file},
+    description::{Block, Description, Pick},
+    examples::{self, Example, ExampleInput},
+    Params, Puzzle,
+};
+
+/// Number of lines shown of every code block.
+const PREVIEW_LINES: usize = 3;
+/// Number of characters shown of every line of a code block.
+const PREVIEW_WIDTH: usize = 60;
+
+/// Parses a comma-separated list of code blocks, e.g. `1,3`.
+pub fn parse_picks(s: &str) -> Result<Vec<usize>, String> {
+    s.split(',')
+        .map(|x| match x.trim().parse() {
+            Ok(index) if index > 0 => Ok(index),
+            _ => Err(format!("expecting code blocks like `1,3`, got `{s}`.")),
+        })
+        .collect()
+}
+
+pub fn handle(puzzle: Puzzle, picks: Option<Vec<usize>>, force: bool) {
+    let puzzle_path = get_puzzle_path(puzzle);
+    let description = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => Description::parse(&markdown),
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle description \"{}\": {e}. Run `cargo read {}` first.",
+                puzzle_path.display(),
+                puzzle.day
+            );
+            process::exit(1);
+        }
+    };
+
+    if description.blocks.is_empty() {
+        eprintln!("No code blocks found in \"{}\".", puzzle_path.display());
+        process::exit(1);
+    }
+
+    let is_interactive = picks.is_none();
+    let picks = match picks {
+        Some(picks) => picks,
+        None if io::stdin().is_terminal() => {
+            print_blocks(&description, &puzzle_path);
+            choose_blocks(&description)
+        }
+        None => {
+            print_blocks(&description, &puzzle_path);
+            eprintln!("Pass the code blocks to extract with `--pick`, e.g. `--pick 1,3`.");
+            process::exit(1);
+        }
+    };
+
+    let mut picked = vec![];
+    for index in picks {
+        let Some(mut pick) = description.pick(index) else {
+            eprintln!(
+                "There is no code block {index}, the description has {}.",
+                description.blocks.len()
+            );
+            process::exit(1);
+        };
+        if is_interactive {
+            edit_answers(index, &mut pick);
+        }
+        picked.push(pick);
+    }
+
+    if let Err(e) = write_examples(puzzle, &picked, force) {
+        eprintln!("Failed to write examples: {e}");
+        process::exit(1);
+    }
+}
+
+fn print_blocks(description: &Description, puzzle_path: &Path) {
+    println!(
+        "Found {} code block(s) in \"{}\":",
+        description.blocks.len(),
+        puzzle_path.display()
+    );
+
+    for block in &description.blocks {
+        println!();
+        println!("{}", block_title(block));
+
+        let lines: Vec<&str> = block.content.lines().collect();
+        for line in lines.iter().take(PREVIEW_LINES) {
+            match line.char_indices().nth(PREVIEW_WIDTH) {
+                Some((end, _)) => println!("    {}…", &line[..end]),
+                None => println!("    {line}"),
+            }
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    …");
+        }
+    }
+
+    println!();
+}
+
+/// Title of a code block, e.g. `[1] part 1, 4 lines, followed by 142`.
+fn block_title(block: &Block) -> String {
+    let lines = block.content.lines().count();
+    let mut title = format!("[{}] part {}, {lines} line(s)", block.index, block.part);
+    if !block.nearby.is_empty() {
+        title.push_str(&format!(", followed by {}", block.nearby.join(", ")));
+    }
+    title
+}
+
+/// Reads a line from stdin after printing `message`, [`None`] at the end of the input.
+fn prompt(message: &str) -> Option<String> {
+    print!("{message}");
+    io::stdout().flush().ok()?;
+
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+fn choose_blocks(description: &Description) -> Vec<usize> {
+    let suggested = description.suggested();
+    let suggested_list = suggested
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    loop {
+        let Some(line) = prompt(&format!("Code blocks to extract [{suggested_list}]: ")) else {
+            process::exit(1);
+        };
+        if line.is_empty() {
+            return suggested;
+        }
+        match parse_picks(&line) {
+            Ok(picks) => return picks,
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// Asks for the expected answers of a picked code block. Enter keeps the guess, `-` removes it.
+fn edit_answers(index: usize, pick: &mut Pick) {
+    for (part, answer) in [(1, &mut pick.part_one), (2, &mut pick.part_two)] {
+        let guess = answer.as_deref().unwrap_or("none");
+        let Some(line) = prompt(&format!(
+            "Answer of code block {index} for part {part} [{guess}, `-` for none]: "
+        )) else {
+            process::exit(1);
+        };
+        match line.as_str() {
+            "" => {}
+            "-" => *answer = None,
+            _ => *answer = Some(line),
+        }
+    }
+}
+
+/// Writes the picked examples to `<day>-<n>.txt` files and adds them to the examples file.
+///
+/// An examples file without any expected answers, as created by `cargo scaffold`, is replaced along with its empty
+/// example files. Otherwise, new examples are appended and examples with an existing input are skipped.
+fn write_examples(puzzle: Puzzle, picks: &[Pick], force: bool) -> Result<(), String> {
+    let dir = puzzle.year.data_dir().join("examples");
+    let examples_path = puzzle.data_path("examples", "", "toml");
+
+    let existing = if examples_path.exists() {
+        examples::read(puzzle)?
+    } else {
+        vec![]
+    };
+    let is_scaffold = existing.iter().all(|e| e.expected().is_empty());
+
+    let mut known_inputs = vec![];
+    let mut known_files = vec![];
+    if !is_scaffold {
+        for example in &existing {
+            known_inputs.push(example.read_input(puzzle)?);
+            if let ExampleInput::File(file) = &example.input {
+                known_files.push(file.clone());
+            }
+        }
+    }
+
+    let mut files: Vec<(PathBuf, &str)> = vec![];
+    let mut added = vec![];
+    let mut number = 0;
+    for pick in picks {
+        if known_inputs.contains(&pick.input) {
+            println!(
+                "Skipping an example that is already in \"{}\"",
+                examples_path.display()
+            );
+            continue;
+        }
+        known_inputs.push(pick.input.clone());
+
+        // NOTE: when appending, files of other examples and files with contents are left alone.
+        let (file, path, current) = loop {
+            number += 1;
+            let file = format!("{}-{number}.txt", puzzle.day);
+            let path = dir.join(&file);
+            let current = fs::read_to_string(&path).unwrap_or_default();
+            if is_scaffold || (!known_files.contains(&file) && current.trim().is_empty()) {
+                break (file, path, current);
+            }
+        };
+
+        if !force && !current.trim().is_empty() && current != pick.input {
+            return Err(format!(
+                "\"{}\" already exists, pass `--force` to overwrite it.",
+                path.display()
+            ));
+        }
+
+        files.push((path, &pick.input));
+        added.push(Example {
+            name: None,
+            input: ExampleInput::File(file),
+            part_one: pick.part_one.clone(),
+            part_two: pick.part_two.clone(),
+            params: Params::new(),
+        });
+    }
+
+    if added.is_empty() {
+        println!("No new examples to add.");
+        return Ok(());
+    }
+
+    for (path, input) in files {
+        write_file(&path, input).map_err(|e| e.to_string())?;
+        println!("Wrote example file \"{}\"", path.display());
+    }
+
+    let mut toml = if is_scaffold {
+        String::from(examples::HEADER)
+    } else {
+        fs::read_to_string(&examples_path).map_err(|e| e.to_string())?
+    };
+    for example in &added {
+        toml.push('\n');
+        toml.push_str(&example.to_toml());
+    }
+    write_file(&examples_path, &toml).map_err(|e| e.to_string())?;
+
+    println!(
+        "Added {} example(s) to \"{}\"",
+        added.len(),
+        examples_path.display()
+    );
+    Ok(())
+}
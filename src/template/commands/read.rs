@@ -1,15 +1,37 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{
+    get_puzzle_path, has_part_two, write_file, AocClient, AocClientError,
+};
+use crate::template::fetch_log::FetchLog;
+use crate::template::submissions::now;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Prints the description of `puzzle`. With `wants_part_two`, a downloaded description without part two is fetched again.
+pub fn handle(puzzle: Puzzle, wants_part_two: bool) {
+    if let Err(e) = read(puzzle, wants_part_two) {
+        eprintln!("failed to read puzzle {puzzle}: {e}");
         process::exit(1);
+    };
+}
+
+fn read(puzzle: Puzzle, wants_part_two: bool) -> Result<(), AocClientError> {
+    let puzzle_path = get_puzzle_path(puzzle);
+    if let Some(description) = fs::read_to_string(&puzzle_path).ok().filter(|description| {
+        !description.trim().is_empty() && (!wants_part_two || has_part_two(description))
+    }) {
+        println!("{description}");
+        return Ok(());
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let client = AocClient::from_env()?;
+    client.check_unlocked(puzzle, now())?;
+
+    println!("Fetching puzzle for day {}, {}...", puzzle.day, puzzle.year);
+    let mut log = FetchLog::read_from_file();
+    let description = log.throttled(puzzle, "puzzle", || client.get_puzzle(puzzle))?;
+    write_file(&puzzle_path, &description)?;
+
+    println!("{description}");
+    Ok(())
 }
@@ -1,28 +1,81 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client::get_puzzle_path,
+    description::Description,
+    examples,
+    templates::{self, DEFAULT_TEMPLATE},
+    Puzzle,
+};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Contents of a new examples file, with one example for each of the example files created by the scaffold.
+fn examples_template(puzzle: Puzzle) -> String {
+    let mut template = String::from(examples::HEADER);
+    for (part, key) in [(1, "part_one"), (2, "part_two")] {
+        template.push_str(&format!(
+            "\n[[example]]\nfile = \"{}-{part}.txt\"\n# {key} =\n",
+            puzzle.day
+        ));
+    }
+    template
+}
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Creates an empty file along with its folders, returns `false` if the file already exists.
+fn create_empty_file(path: &Path) -> Result<bool, std::io::Error> {
+    // NOTE: the data folders of a year are created on first use.
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match safe_create_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Renders the template of the module, with the puzzle title if its description was downloaded.
+fn module_contents(puzzle: Puzzle, template: &str) -> Result<String, String> {
+    let title = fs::read_to_string(get_puzzle_path(puzzle))
+        .ok()
+        .and_then(|markdown| Description::parse(&markdown).title);
+    templates::render(&templates::load(template)?, puzzle, title.as_deref())
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = |part| format!("data/examples/{day}-{part}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module of a day from `template` along with its data files. An existing module is never overwritten,
+/// data files that already exist are kept.
+pub fn handle(puzzle: Puzzle, template: Option<&str>) {
+    let template = template.unwrap_or(DEFAULT_TEMPLATE);
+    let input_path = puzzle.data_path("inputs", "", "txt");
+    let example_path = |part| puzzle.data_path("examples", &format!("-{part}"), "txt");
+    let examples_path = puzzle.data_path("examples", "", "toml");
+    let module_path = puzzle.bin_path();
+
+    if Path::new(&module_path).exists() {
+        eprintln!(
+            "Module file \"{module_path}\" already exists, remove it to scaffold day {} again.",
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let contents = match module_contents(puzzle, template) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Failed to render template `{template}`: {e}");
+            process::exit(1);
+        }
+    };
 
-    let mut file = match safe_create_file(&module_path) {
+    let mut file = match safe_create_file(Path::new(&module_path)) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -30,13 +83,9 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{module_path}\" from template `{template}`");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -44,29 +93,45 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let data_files = [
+        ("input", input_path),
+        ("example", example_path(1)),
+        ("example", example_path(2)),
+    ];
+    for (kind, file_path) in data_files {
+        match create_empty_file(&file_path) {
+            Ok(true) => println!("Created empty {kind} file \"{}\"", file_path.display()),
+            Ok(false) => println!("Kept existing {kind} file \"{}\"", file_path.display()),
+            Err(e) => {
+                eprintln!("Failed to create {kind} file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    for part in 1..3 {
-        let file_path = example_path(part);
-        match create_file(&file_path) {
-            Ok(_) => {
-                println!("Created empty example file \"{}\"", &file_path);
-            }
+    match safe_create_file(&examples_path) {
+        Ok(mut file) => match file.write_all(examples_template(puzzle).as_bytes()) {
+            Ok(()) => println!("Created examples file \"{}\"", examples_path.display()),
             Err(e) => {
-                eprintln!("Failed to create example file: {e}");
+                eprintln!("Failed to write examples file: {e}");
                 process::exit(1);
             }
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            println!(
+                "Kept existing examples file \"{}\"",
+                examples_path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to create examples file: {e}");
+            process::exit(1);
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
@@ -1,28 +1,79 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{BenchConfig, Params, Puzzle};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options of `cargo solve`, forwarded to the solution binary.
+pub struct SolveOptions {
+    pub release: bool,
+    /// Profile heap allocations with DHAT.
+    pub dhat: bool,
+    /// Count the heap allocations of every part.
+    pub heap_stats: bool,
+    /// Part whose answer is submitted.
+    pub submit: Option<u8>,
+    /// Record the answers in the answer ledger.
+    pub record: bool,
+    /// Check the examples instead of the input.
+    pub examples: bool,
+    pub params: Params,
+    /// Bench the solution with this budget.
+    pub bench_config: Option<BenchConfig>,
+}
+
+pub fn handle(puzzle: Puzzle, options: &SolveOptions) {
+    let SolveOptions {
+        release,
+        dhat,
+        heap_stats,
+        submit,
+        record,
+        examples,
+        params,
+        bench_config,
+    } = options;
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
-    if dhat {
+    if *dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if *release {
+            cmd_args.push("--release".to_string());
+        }
+        if *heap_stats {
+            cmd_args.push("--features".to_string());
+            cmd_args.push("heap-stats".to_string());
+        }
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    // examples are checked against their expected answers, they are never submitted, recorded or benched.
+    if *examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    cmd_args.extend(params.to_args());
+
+    if let Some(submit_part) = submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if *record {
+        cmd_args.push("--record".to_string());
+    }
+
+    if let Some(bench_config) = bench_config {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench_config.to_args());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +81,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // NOTE: forward the exit code so that failing parts fail the command, too.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,16 @@
+use std::process;
+
+use crate::template::{answers::Answers, readme_stars, Year};
+
+/// Writes the stars of a year to the readme, based on the answers in `data/<year>/answers.json`.
+pub fn handle(year: Year) {
+    let answers = Answers::read_from_file(year);
+
+    match readme_stars::update(year, &answers) {
+        Ok(()) => println!("Stored updated stars."),
+        Err(e) => {
+            eprintln!("Failed to store updated stars: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,11 +1,33 @@
 use std::collections::HashSet;
+use std::process::{self, Command};
 
-use crate::template::run_multi::run_multi;
+use crate::template::run_multi::{run_multi, MultiRunOptions};
+use crate::template::submissions::now;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options for keeping track of benchmarks over time.
+pub struct HistoryOptions {
+    /// Name the stored run can later be compared against.
+    pub label: Option<String>,
+    /// Compare the run with the previously stored run of each day.
+    pub compare: bool,
+    /// Compare with the latest run that has this label or commit instead.
+    pub baseline: Option<String>,
+    /// Slowdown in percent above which a part is considered a regression.
+    pub threshold: f64,
+}
+
+/// Benches the given day, or all days that are not fully benched yet. `options` must have a `bench_config`.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    history: &HistoryOptions,
+    options: &MultiRunOptions,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,20 +43,93 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut run = run_multi(year, &days_to_run, options);
+    let timings = run.timings.take().unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let mut merged_timings = stored_timings.merge(&timings);
+        merged_timings.push_history(
+            &timings,
+            now(),
+            current_commit().as_deref(),
+            history.label.as_deref(),
+        );
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    let mut regressions = 0;
+    if history.compare || history.baseline.is_some() {
+        regressions = print_comparison(&stored_timings, &timings, history);
+    }
+
+    if regressions > 0 || run.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// Prints how the timings of this run changed compared to the baseline, returns the number of regressions.
+fn print_comparison(stored: &Timings, timings: &Timings, history: &HistoryOptions) -> usize {
+    let baseline = history.baseline.as_deref();
+    let comparisons = stored.compare(timings, baseline);
+
+    let title = match baseline {
+        Some(baseline) => format!("Comparison with `{baseline}`"),
+        None => "Comparison with previous run".into(),
+    };
+
+    println!();
+    println!("{ANSI_BOLD}{title}{ANSI_RESET}");
+    println!("{}", "-".repeat(title.chars().count()));
+
+    for day in timings.data.iter().map(|t| t.day) {
+        if !comparisons.iter().any(|c| c.day == day) {
+            println!("Day {day}: no baseline.");
+        }
+    }
+
+    let mut regressions = 0;
+
+    for comparison in &comparisons {
+        let is_regression = comparison.is_regression(history.threshold);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "Day {} Part {}: {:.1?} → {:.1?} ({:+.1}%){}",
+            comparison.day,
+            comparison.part,
+            comparison.before,
+            comparison.after,
+            comparison.change_percent(),
+            if is_regression { " ⚠ regression" } else { "" }
+        );
+    }
+
+    println!("{regressions} regression(s) above {}%.", history.threshold);
+
+    regressions
+}
+
+/// Short hash of the current `HEAD` commit, [`None`] outside of a git repository.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
@@ -0,0 +1,72 @@
+use std::process;
+
+use crate::template::{
+    all_days,
+    answers::{Answers, Verdict},
+    run_multi::{run_multi, MultiRunOptions},
+    Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Runs every day of a year and compares the answers with the ledger in `data/<year>/answers.json`.
+/// Exits with a non-zero code if any answer differs from its recorded answer.
+pub fn handle(year: Year, options: &MultiRunOptions) {
+    let answers = Answers::read_from_file(year);
+    let run = run_multi(year, &all_days().collect(), options);
+
+    let mut matches = 0;
+    let mut unrecorded = 0;
+    let mut mismatches: Vec<String> = vec![];
+
+    for record in &run.records {
+        match answers.verify(record.day, record.part, record.answer.as_deref()) {
+            Verdict::Match => matches += 1,
+            Verdict::Unrecorded => unrecorded += 1,
+            Verdict::Mismatch { expected } => mismatches.push(match &record.error {
+                Some(error) => format!(
+                    "Day {} Part {}: expected `{expected}`, but the part failed: {error}",
+                    record.day, record.part
+                ),
+                None => format!(
+                    "Day {} Part {}: expected `{expected}`, got `{}`.",
+                    record.day,
+                    record.part,
+                    record.answer.as_deref().unwrap_or("✖")
+                ),
+            }),
+        }
+    }
+
+    // recorded answers of days that did not produce any output at all.
+    for answer in &answers.data {
+        for part in 1..=2 {
+            let was_run = run
+                .records
+                .iter()
+                .any(|r| r.day == answer.day && r.part == part);
+
+            if let (Some(expected), false) = (answer.get(part), was_run) {
+                mismatches.push(format!(
+                    "Day {} Part {part}: expected `{expected}`, but the part did not run.",
+                    answer.day
+                ));
+            }
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+
+    println!(
+        "{matches} matching, {} mismatched, {unrecorded} unrecorded answer(s).",
+        mismatches.len()
+    );
+
+    if !mismatches.is_empty() || run.has_failures() {
+        process::exit(1);
+    }
+}
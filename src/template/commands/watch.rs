@@ -0,0 +1,281 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    output,
+    run_multi::{child_commands::run_solution, Failure},
+    runner::print_record,
+    MultiRunOptions, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Clears the terminal and moves the cursor to the top left corner.
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Outcome of running the tests of a solution against its examples.
+enum TestRun {
+    /// The tests did not compile, holds the compiler output.
+    BuildFailed(String),
+    /// The tests ran, holds the output of failed tests if there are any.
+    Finished {
+        passed: usize,
+        failed: usize,
+        failures: String,
+    },
+}
+
+/// Reruns the tests and the solution of a day whenever its solution, the shared library or its data change.
+pub fn handle(puzzle: Puzzle, options: &MultiRunOptions) {
+    if !Path::new(&puzzle.bin_path()).exists() {
+        eprintln!(
+            "Solution for day {} does not exist, scaffold it first.",
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let mut previous = None;
+
+    loop {
+        if previous.as_ref() != Some(&modified_times(puzzle)) {
+            // editors may save a file in several steps, give them a moment before taking the snapshot.
+            thread::sleep(POLL_INTERVAL);
+            previous = Some(modified_times(puzzle));
+            run(puzzle, options);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Files that trigger a new run: the solution, the shared library, the input and all examples of the day.
+fn watched_files(puzzle: Puzzle) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(puzzle.bin_path()),
+        puzzle.data_path("inputs", "", "txt"),
+    ];
+    library_files(Path::new("src"), &mut files);
+
+    let day = puzzle.day.to_string();
+    let examples_dir = puzzle.year.data_dir().join("examples");
+
+    if let Ok(entries) = fs::read_dir(examples_dir) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| stem == day || stem.starts_with(&format!("{day}-")))
+                }),
+        );
+    }
+
+    files
+}
+
+/// Collects the files of the library in `dir` and its subdirectories. Solutions in `src/bin` are left out,
+/// only the solution of the watched day is relevant.
+fn library_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.is_dir() {
+            if path != Path::new("src/bin") {
+                library_files(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Modification times of the watched files. Files that do not exist are left out, so creating or deleting one counts as a change.
+fn modified_times(puzzle: Puzzle) -> BTreeMap<PathBuf, SystemTime> {
+    watched_files(puzzle)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Runs the tests of the day, then the solution against the real input, and prints a panel with the results.
+fn run(puzzle: Puzzle, options: &MultiRunOptions) {
+    print!("{ANSI_CLEAR}");
+    println!("Day {}: {ANSI_ITALIC}running...{ANSI_RESET}", puzzle.day);
+
+    let tests = run_tests(puzzle);
+
+    // a day whose tests do not compile can not be run either, the compiler output is shown instead.
+    let has_input = puzzle.data_path("inputs", "", "txt").exists();
+    let solution = (has_input && !matches!(tests, TestRun::BuildFailed(_)))
+        .then(|| output::capture(|| run_solution(puzzle, options)));
+
+    print!("{ANSI_CLEAR}");
+    println!(
+        "{ANSI_BOLD}Day {}, {}{ANSI_RESET} - watching for changes, press Ctrl+C to stop.",
+        puzzle.day, puzzle.year
+    );
+    println!();
+
+    match &tests {
+        TestRun::BuildFailed(_) => println!("Examples: {ANSI_BOLD}build failed{ANSI_RESET}"),
+        TestRun::Finished {
+            passed, failed: 0, ..
+        } => println!("Examples: ✔ {passed} passed"),
+        TestRun::Finished { passed, failed, .. } => {
+            println!("Examples: {ANSI_BOLD}✖ {failed} failed{ANSI_RESET}, {passed} passed");
+        }
+    }
+
+    match solution {
+        None if !has_input => println!(
+            "Input: missing, download it with `cargo download {}`.",
+            puzzle.day
+        ),
+        None => {}
+        Some((Err(e), output)) => {
+            println!("Input: {ANSI_BOLD}error{ANSI_RESET}: {e}");
+            print!("{output}");
+        }
+        Some((Ok(day_run), output)) => {
+            day_run.records.parts.iter().for_each(print_record);
+
+            // the regular output of the solution is only shown if it did not finish, it holds the reason.
+            if let Some(failure) = day_run.failure {
+                match failure {
+                    Failure::TimedOut => println!(
+                        "Input: {ANSI_BOLD}timed out{ANSI_RESET} after {:?}.",
+                        options.limits.timeout.unwrap_or_default()
+                    ),
+                    _ => println!("Input: {ANSI_BOLD}{failure}{ANSI_RESET}"),
+                }
+                println!();
+                print!("{output}");
+            }
+        }
+    }
+
+    match tests {
+        TestRun::BuildFailed(output) => {
+            println!();
+            print!("{output}");
+        }
+        TestRun::Finished { failures, .. } if !failures.is_empty() => {
+            println!();
+            print!("{failures}");
+        }
+        TestRun::Finished { .. } => {}
+    }
+}
+
+/// Runs the tests of the solution binary, which check the parts against the examples.
+fn run_tests(puzzle: Puzzle) -> TestRun {
+    let output = match Command::new("cargo")
+        .args(["test", "--quiet", "--color", "never", "--bin"])
+        .arg(puzzle.to_string())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => return TestRun::BuildFailed(format!("failed to run cargo: {e}\n")),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    match parse_test_summary(&stdout) {
+        Some((passed, failed)) => TestRun::Finished {
+            passed,
+            failed,
+            failures: test_failures(&stdout),
+        },
+        None => TestRun::BuildFailed(String::from_utf8_lossy(&output.stderr).into_owned()),
+    }
+}
+
+/// Reads the number of passed and failed tests from the summary line of the test harness,
+/// e.g. `test result: FAILED. 1 passed; 1 failed; 0 ignored; ...`.
+fn parse_test_summary(stdout: &str) -> Option<(usize, usize)> {
+    let summary = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("test result: "))?;
+
+    let count = |label: &str| {
+        summary.split(';').find_map(|x| {
+            let mut words = x.split_whitespace().rev();
+            (words.next()? == label).then(|| words.next()?.parse().ok())?
+        })
+    };
+
+    Some((count("passed")?, count("failed")?))
+}
+
+/// Extracts the output of failed tests, i.e. the first `failures:` section of the test harness.
+fn test_failures(stdout: &str) -> String {
+    let lines: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| *line != "failures:")
+        .skip(1)
+        .take_while(|line| *line != "failures:")
+        .collect();
+
+    match lines.join("\n").trim() {
+        "" => String::new(),
+        failures => format!("{failures}\n"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_test_summary, test_failures};
+
+    const OUTPUT: &str = "
+running 2 tests
+.F
+failures:
+
+---- tests::test_part_two stdout ----
+thread 'tests::test_part_two' panicked at src/bin/2023_01.rs:12:9:
+assertion `left == right` failed
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    #[test]
+    fn parses_test_summary() {
+        assert_eq!(parse_test_summary(OUTPUT), Some((1, 1)));
+        assert_eq!(
+            parse_test_summary("test result: ok. 2 passed; 0 failed; 0 ignored"),
+            Some((2, 0))
+        );
+        assert_eq!(parse_test_summary("error: could not compile"), None);
+    }
+
+    #[test]
+    fn extracts_test_failures() {
+        assert_eq!(
+            test_failures(OUTPUT),
+            "---- tests::test_part_two stdout ----\n\
+            thread 'tests::test_part_two' panicked at src/bin/2023_01.rs:12:9:\n\
+            assertion `left == right` failed\n"
+        );
+        assert_eq!(test_failures("test result: ok. 2 passed; 0 failed"), "");
+    }
+}
@@ -5,7 +5,7 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
+/// Puzzles unlock at midnight in the timezone of the Advent of Code server.
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
@@ -41,6 +41,22 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the unix timestamp at which the puzzle of this day unlocks in the given year.
+    pub fn unlock_timestamp(self, year: u16) -> i64 {
+        let days = days_from_civil(i64::from(year), 12, i64::from(self.0));
+        days * 86400 - i64::from(SERVER_UTC_OFFSET) * 3600
+    }
+}
+
+/// Number of days since the unix epoch of a date in the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
 }
 
 #[cfg(feature = "today")]
@@ -187,6 +203,14 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn unlock_timestamp() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(Day(1).unlock_timestamp(2023), 1_701_406_800);
+        // 2020-12-25T05:00:00Z
+        assert_eq!(Day(25).unlock_timestamp(2020), 1_608_872_400);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,207 @@
+//! Finds example inputs and their answers in puzzle descriptions, as written to `data/<year>/puzzles/<day>.md`.
+//!
+//! Descriptions show example inputs as code blocks and highlight answers as emphasized code, `` `*142*` `` in markdown.
+//! The first block of a part is almost always its example input, and the last highlighted value of a part is its answer.
+use regex::Regex;
+
+/// A code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// Position of the block in the description, starting at 1.
+    pub index: usize,
+    /// Part of the puzzle the block belongs to.
+    pub part: u8,
+    pub content: String,
+    /// Highlighted values between this block and the next one.
+    pub nearby: Vec<String>,
+}
+
+/// Code blocks and highlighted answers of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Description {
+    /// Title of the puzzle, e.g. `Trebuchet?!`.
+    pub title: Option<String>,
+    pub blocks: Vec<Block>,
+    /// The last highlighted value of each part, usually the answer of its example.
+    pub answers: [Option<String>; 2],
+}
+
+/// An example input picked from a description, with the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pick {
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Description {
+    pub fn parse(markdown: &str) -> Self {
+        let answer_re = Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap();
+
+        let mut description = Self::default();
+        let mut part = 1;
+        let mut code: Option<String> = None;
+
+        for line in markdown.lines() {
+            if line.starts_with("```") {
+                match code.take() {
+                    Some(content) => description.blocks.push(Block {
+                        index: description.blocks.len() + 1,
+                        part,
+                        content,
+                        nearby: vec![],
+                    }),
+                    None => code = Some(String::new()),
+                }
+                continue;
+            }
+
+            if let Some(content) = code.as_mut() {
+                content.push_str(line);
+                content.push('\n');
+                continue;
+            }
+
+            if let Some(heading) = line.strip_prefix("## --- ") {
+                if heading.starts_with("Part Two") {
+                    part = 2;
+                } else if let Some((_, title)) = heading.split_once(": ") {
+                    let title = title.trim_end_matches('-').trim();
+                    description.title.get_or_insert_with(|| title.to_string());
+                }
+                continue;
+            }
+
+            for captures in answer_re.captures_iter(line) {
+                let answer = captures.get(1).or(captures.get(2)).unwrap().as_str();
+                description.answers[part as usize - 1] = Some(answer.to_string());
+
+                if let Some(block) = description.blocks.last_mut().filter(|b| b.part == part) {
+                    block.nearby.push(answer.to_string());
+                }
+            }
+        }
+
+        description
+    }
+
+    /// The block at `index`, starting at 1.
+    pub fn block(&self, index: usize) -> Option<&Block> {
+        self.blocks.get(index.checked_sub(1)?)
+    }
+
+    fn is_first_of_part(&self, block: &Block) -> bool {
+        self.blocks.iter().find(|b| b.part == block.part) == Some(block)
+    }
+
+    /// Picks the block at `index` as an example and guesses its answers.
+    ///
+    /// The first block of a part is expected to produce the last answer of that part, other blocks the last value
+    /// highlighted after them. If part two has no blocks of its own, it reuses the example of part one.
+    pub fn pick(&self, index: usize) -> Option<Pick> {
+        let block = self.block(index)?;
+
+        let own_answer = if self.is_first_of_part(block) {
+            self.answers[block.part as usize - 1].clone()
+        } else {
+            block.nearby.last().cloned()
+        };
+
+        let reuses_example = block.part == 1
+            && self.is_first_of_part(block)
+            && !self.blocks.iter().any(|b| b.part == 2);
+
+        let (part_one, part_two) = match block.part {
+            1 if reuses_example => (own_answer, self.answers[1].clone()),
+            1 => (own_answer, None),
+            _ => (None, own_answer),
+        };
+
+        Some(Pick {
+            input: block.content.clone(),
+            part_one,
+            part_two,
+        })
+    }
+
+    /// Indices of the blocks that most likely are the example inputs, the first block of each part.
+    pub fn suggested(&self) -> Vec<usize> {
+        self.blocks
+            .iter()
+            .filter(|b| self.is_first_of_part(b))
+            .map(|b| b.index)
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Description, Pick};
+
+    const DAY_1: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these lines are `12` and `38`. Adding these together produces `*50*`.
+
+## --- Part Two ---
+
+In this example, the calibration values are `*29*` and `83`:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn finds_blocks_and_answers() {
+        let description = Description::parse(DAY_1);
+
+        assert_eq!(description.title.as_deref(), Some("Trebuchet?!"));
+        assert_eq!(description.blocks.len(), 2);
+        assert_eq!(description.blocks[0].content, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(description.blocks[0].nearby, vec!["50"]);
+        assert_eq!(description.blocks[1].part, 2);
+        assert_eq!(description.blocks[1].nearby, vec!["281"]);
+        assert_eq!(description.answers, [Some("50".into()), Some("281".into())]);
+        assert_eq!(description.suggested(), vec![1, 2]);
+
+        assert_eq!(
+            description.pick(2),
+            Some(Pick {
+                input: "two1nine\neightwothree\n".into(),
+                part_one: None,
+                part_two: Some("281".into()),
+            })
+        );
+        assert_eq!(description.pick(3), None);
+        assert_eq!(description.pick(0), None);
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let description = Description::parse(
+            "```\n...#\n#...\n```\n\n```\n....#\n```\n\nThe sum is `*374*`.\n\n\
+            ## --- Part Two ---\n\nThe sum is `*1030*`, or `*8410*` when larger.\n",
+        );
+
+        let pick = description.pick(1).unwrap();
+        assert_eq!(pick.part_one.as_deref(), Some("374"));
+        assert_eq!(pick.part_two.as_deref(), Some("8410"));
+
+        let pick = description.pick(2).unwrap();
+        assert_eq!(pick.part_one.as_deref(), Some("374"));
+        assert_eq!(pick.part_two, None);
+        assert_eq!(description.suggested(), vec![1]);
+    }
+}
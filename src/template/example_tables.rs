@@ -0,0 +1,30 @@
+//! Reads the `[[example]]` tables of an examples file, see [`examples`](super::examples) for their keys.
+//!
+//! `build.rs` includes this module to count the examples of every day, so it must only depend on `toml`.
+use toml::{Table, Value};
+
+/// Parses an examples file into its `[[example]]` tables, in order of the file.
+pub fn parse_tables(s: &str) -> Result<Vec<Table>, String> {
+    let mut document: Table = s.parse().map_err(|e: toml::de::Error| e.to_string())?;
+
+    let examples = match document.remove("example") {
+        None => vec![],
+        Some(Value::Array(examples)) => examples,
+        Some(_) => return Err("expected `example` to be an array of `[[example]]` tables".into()),
+    };
+
+    if let Some(key) = document.keys().next() {
+        return Err(format!(
+            "unexpected key `{key}`, expected only `[[example]]` tables"
+        ));
+    }
+
+    examples
+        .into_iter()
+        .enumerate()
+        .map(|(i, example)| match example {
+            Value::Table(table) => Ok(table),
+            _ => Err(format!("expected example {} to be a table", i + 1)),
+        })
+        .collect()
+}
@@ -0,0 +1,426 @@
+//! Example inputs of a puzzle along with their expected answers, stored in `data/<year>/examples/<day>.toml`.
+//!
+//! The file is a list of `[[example]]` tables in TOML, each may have an `[example.params]` table:
+//! ```toml
+//! [[example]]
+//! name = "enclosed loop"
+//! file = "10-3.txt"
+//! part_two = 4
+//!
+//! [[example]]
+//! input = """
+//! .....
+//! .S-7.
+//! """
+//! part_one = 4
+//! ```
+//! The build script generates one test per example, see the [`solution!`](crate::solution) macro.
+use std::{fs, process};
+
+use toml::{Table, Value};
+
+use crate::template::{
+    example_tables::parse_tables,
+    output::{self, outln},
+    protocol::{PartRecord, PART_FAILED_EXIT_CODE},
+    runner::{RunOptions, Solution},
+    Params, Puzzle, ANSI_BOLD, ANSI_RESET,
+};
+
+/// First line of new examples files.
+pub const HEADER: &str =
+    "# Examples of this day along with their expected answers. Each example becomes a test of the solution.\n";
+
+/// Where the input of an example comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExampleInput {
+    /// A file in the examples folder of the year, e.g. `10-3.txt`.
+    File(String),
+    /// The input itself, usually a multi-line string.
+    Inline(String),
+}
+
+/// A single example of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: Option<String>,
+    pub input: ExampleInput,
+    /// Expected answer of part one, the part is not run for this example if there is none.
+    pub part_one: Option<String>,
+    /// Expected answer of part two, the part is not run for this example if there is none.
+    pub part_two: Option<String>,
+    /// Parameters of the example, e.g. a number of steps that differs from the real input.
+    pub params: Params,
+}
+
+impl Example {
+    /// Expected answers by part, in order of parts.
+    pub fn expected(&self) -> Vec<(u8, &str)> {
+        [(1, &self.part_one), (2, &self.part_two)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_deref()?)))
+            .collect()
+    }
+
+    /// Reads the input of the example, relative to the examples folder of the puzzle's year.
+    pub fn read_input(&self, puzzle: Puzzle) -> Result<String, String> {
+        match &self.input {
+            ExampleInput::Inline(input) => Ok(input.clone()),
+            ExampleInput::File(file) => {
+                let path = puzzle.year.data_dir().join("examples").join(file);
+                fs::read_to_string(&path)
+                    .map_err(|e| format!("could not read {}: {e}", path.display()))
+            }
+        }
+    }
+
+    /// Title of the example, e.g. `Example 3 (enclosed loop)`. `index` starts at 1.
+    fn title(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => format!("Example {index} ({name})"),
+            None => format!("Example {index}"),
+        }
+    }
+
+    /// Formats the example as an `[[example]]` table of an examples file.
+    pub fn to_toml(&self) -> String {
+        let mut table = Table::new();
+
+        if let Some(name) = &self.name {
+            table.insert("name".into(), Value::String(name.clone()));
+        }
+        let (key, input) = match &self.input {
+            ExampleInput::File(file) => ("file", file),
+            ExampleInput::Inline(input) => ("input", input),
+        };
+        table.insert(key.into(), Value::String(input.clone()));
+        for (key, answer) in [("part_one", &self.part_one), ("part_two", &self.part_two)] {
+            if let Some(answer) = answer {
+                table.insert(key.into(), to_value(answer));
+            }
+        }
+        if !self.params.is_empty() {
+            let params = self.params.iter().map(|(k, v)| (k.into(), to_value(v)));
+            table.insert("params".into(), Value::Table(params.collect()));
+        }
+
+        let mut document = Table::new();
+        document.insert("example".into(), Value::Array(vec![Value::Table(table)]));
+        toml::to_string_pretty(&document).expect("examples can always be formatted")
+    }
+
+    /// Reads an example from its `[[example]]` table.
+    fn from_table(table: Table) -> Result<Self, String> {
+        let mut example = Self {
+            name: None,
+            input: ExampleInput::File(String::new()),
+            part_one: None,
+            part_two: None,
+            params: Params::new(),
+        };
+        let mut input = None;
+
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("name", value) => example.name = Some(to_answer(&key, value)?),
+                ("file", Value::String(file)) if input.is_none() => {
+                    input = Some(ExampleInput::File(file));
+                }
+                ("input", Value::String(inline)) if input.is_none() => {
+                    input = Some(ExampleInput::Inline(inline));
+                }
+                ("file" | "input", Value::String(_)) => {
+                    return Err("an example has either a `file` or an `input`".into());
+                }
+                ("file" | "input", _) => return Err(format!("expected a string for `{key}`")),
+                ("part_one", value) => example.part_one = Some(to_answer(&key, value)?),
+                ("part_two", value) => example.part_two = Some(to_answer(&key, value)?),
+                ("params", Value::Table(params)) => {
+                    for (key, value) in params {
+                        let value = to_answer(&key, value)?;
+                        example.params.insert(key, value);
+                    }
+                }
+                ("params", _) => return Err("expected `params` to be a table".into()),
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+
+        example.input = input.ok_or("example is missing a `file` or an `input`")?;
+        Ok(example)
+    }
+}
+
+/// Converts a scalar value of an examples file into the answer or parameter it stands for.
+fn to_answer(key: &str, value: Value) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s),
+        Value::Integer(x) => Ok(x.to_string()),
+        Value::Float(x) => Ok(x.to_string()),
+        Value::Boolean(x) => Ok(x.to_string()),
+        _ => Err(format!(
+            "expected a string, number or boolean for `{key}`, got {value}"
+        )),
+    }
+}
+
+/// Converts an answer or parameter into a value that is read back as the same string, e.g. `142` as a number.
+fn to_value(s: &str) -> Value {
+    match s.parse::<i64>() {
+        Ok(x) if x.to_string() == s => Value::Integer(x),
+        _ => Value::String(s.to_string()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the examples of a puzzle from `data/<year>/examples/<day>.toml`.
+pub fn read(puzzle: Puzzle) -> Result<Vec<Example>, String> {
+    let path = puzzle.data_path("examples", "", "toml");
+    let s =
+        fs::read_to_string(&path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    parse(&s).map_err(|e| format!("invalid examples in {}: {e}", path.display()))
+}
+
+/// Runs the parts of an example that have an expected answer, returns a message for every part that differs.
+fn run_example(solution: &Solution, example: &Example) -> Result<Vec<String>, String> {
+    let input = example.read_input(solution.puzzle)?;
+    let expected = example.expected();
+
+    let options = RunOptions {
+        part: match expected.as_slice() {
+            [(part, _)] => Some(*part),
+            _ => None,
+        },
+        params: example.params.clone(),
+        ..RunOptions::default()
+    };
+
+    let records = (solution.run)(&input, &options);
+
+    let mismatches = expected
+        .into_iter()
+        .filter_map(
+            |(part, expected)| match records.parts.iter().find(|r| r.part == part) {
+                None => Some(format!(
+                    "Part {part}: expected `{expected}`, but the solution has no part {part}."
+                )),
+                Some(PartRecord {
+                    error: Some(error), ..
+                }) => Some(format!(
+                    "Part {part}: expected `{expected}`, but the part failed: {error}"
+                )),
+                Some(record) if record.answer.as_deref() != Some(expected) => Some(format!(
+                    "Part {part}: expected `{expected}`, got `{}`.",
+                    record.answer.as_deref().unwrap_or("✖")
+                )),
+                Some(_) => None,
+            },
+        )
+        .collect();
+
+    Ok(mismatches)
+}
+
+/// Runs example `index` of a solution, starting at 1. Panics if it differs from its expected answers.
+/// This is called by the tests that the build script generates for every example.
+pub fn test_example(solution: &Solution, index: usize) {
+    let examples = read(solution.puzzle).unwrap_or_else(|e| panic!("{e}"));
+    let Some(example) = examples.get(index - 1) else {
+        panic!("example {index} does not exist.");
+    };
+
+    if example.expected().is_empty() {
+        println!(
+            "{} has no expected answers, skipping.",
+            example.title(index)
+        );
+        return;
+    }
+
+    // NOTE: the runner writes to stdout directly, which would bypass the output capturing of the test harness.
+    let (mismatches, _) = output::capture(|| run_example(solution, example));
+    let mismatches = mismatches.unwrap_or_else(|e| panic!("{e}"));
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+/// Runs all examples of a solution and prints whether their answers match.
+/// Exits with [`PART_FAILED_EXIT_CODE`] if an example could not be run or differs from its expected answers.
+pub fn run_examples(solution: &Solution) {
+    let examples = match read(solution.puzzle) {
+        Ok(examples) if !examples.is_empty() => examples,
+        Ok(_) => {
+            eprintln!("No examples found for day {}.", solution.puzzle.day);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read examples: {e}");
+            process::exit(1);
+        }
+    };
+
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    for (i, example) in examples.iter().enumerate() {
+        outln!("{ANSI_BOLD}{}{ANSI_RESET}", example.title(i + 1));
+        if !example.params.is_empty() {
+            outln!("Params: {}", example.params);
+        }
+
+        if example.expected().is_empty() {
+            outln!("No expected answers, skipping.");
+            skipped += 1;
+        } else {
+            match run_example(solution, example) {
+                Ok(mismatches) if mismatches.is_empty() => {
+                    outln!("✔ as expected");
+                    passed += 1;
+                }
+                Ok(mismatches) => {
+                    mismatches.iter().for_each(|m| outln!("✖ {m}"));
+                    failed += 1;
+                }
+                Err(e) => {
+                    outln!("✖ {e}");
+                    failed += 1;
+                }
+            }
+        }
+
+        outln!();
+    }
+
+    outln!("{passed} passed, {failed} failed, {skipped} skipped example(s).");
+
+    if failed > 0 {
+        process::exit(PART_FAILED_EXIT_CODE);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses the contents of an examples file, see the [module documentation](self) for its format.
+pub fn parse(s: &str) -> Result<Vec<Example>, String> {
+    parse_tables(s)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, table)| Example::from_table(table).map_err(|e| format!("example {}: {e}", i + 1)))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Example, ExampleInput, Params};
+
+    #[test]
+    fn parses_examples() {
+        let examples = parse(
+            r#"
+# examples of day 10.
+[[example]]
+file = "10-1.txt"
+part_one = 4 # the farthest tile
+
+[[example]] # enclosed tiles
+name = "squeeze"
+input = """
+..........
+.S------7.
+"""
+part_two = "4"
+
+[example.params]
+steps = 1_000
+scale = 0.5
+label = 'C:\path'
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    name: None,
+                    input: ExampleInput::File("10-1.txt".into()),
+                    part_one: Some("4".into()),
+                    part_two: None,
+                    params: Params::new(),
+                },
+                Example {
+                    name: Some("squeeze".into()),
+                    input: ExampleInput::Inline("..........\n.S------7.\n".into()),
+                    part_one: None,
+                    part_two: Some("4".into()),
+                    params: [("label", "C:\\path"), ("scale", "0.5"), ("steps", "1000")]
+                        .into_iter()
+                        .collect(),
+                },
+            ]
+        );
+        assert_eq!(examples[1].expected(), vec![(2, "4")]);
+    }
+
+    #[test]
+    fn parses_strings() {
+        let examples = parse(
+            "[[example]]\ninput = \"a\\\"b\\n\" # comment\n[[example]]\ninput = '''x\\y'''\nname = \"a # b\"",
+        )
+        .unwrap();
+        assert_eq!(examples[0].input, ExampleInput::Inline("a\"b\n".into()));
+        assert_eq!(examples[1].input, ExampleInput::Inline("x\\y".into()));
+        assert_eq!(examples[1].name.as_deref(), Some("a # b"));
+    }
+
+    #[test]
+    fn formats_examples() {
+        let examples = vec![
+            Example {
+                name: Some("a \"quoted\" name".into()),
+                input: ExampleInput::File("10-1.txt".into()),
+                part_one: Some("0042".into()),
+                part_two: Some("142".into()),
+                params: [("steps", "6"), ("label", "x y")].into_iter().collect(),
+            },
+            Example {
+                name: None,
+                input: ExampleInput::Inline("..#\n#''''\\\n".into()),
+                part_one: None,
+                part_two: Some("EFGH".into()),
+                params: Params::new(),
+            },
+        ];
+
+        let toml: Vec<String> = examples.iter().map(Example::to_toml).collect();
+        assert!(toml[0].contains("part_one = \"0042\"\npart_two = 142\n"));
+        assert_eq!(parse(&toml.join("\n")).unwrap(), examples);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let error = |s: &str| parse(s).unwrap_err();
+
+        assert!(error("file = \"01.txt\"").contains("unexpected key `file`"));
+        assert!(error("[[example]]\nfile = \"a\"\nfile = \"b\"").contains("line 3"));
+        assert!(error("[[example]]\ninput = \"\"\"\nabc\n").contains("multi-line basic string"));
+        assert!(error("[[example]]\nfile = \"a\"\npart_one = 1 2").contains("line 3"));
+        assert!(
+            error("[[example]]\nfile = \"a\"\n[examples]").contains("unexpected key `examples`")
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"a\"\n[[example]]\nfile = \"a\"\nanswer = 1"),
+            "example 2: unknown key `answer`"
+        );
+        assert_eq!(
+            error("[[example]]\npart_one = 1"),
+            "example 1: example is missing a `file` or an `input`"
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"a\"\ninput = \"b\""),
+            "example 1: an example has either a `file` or an `input`"
+        );
+        assert!(error("[[example]]\nfile = 'a'\npart_one = [1]").contains("`part_one`"));
+    }
+}
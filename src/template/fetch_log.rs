@@ -0,0 +1,246 @@
+//! Log of requests made to the Advent of Code website, used to throttle downloads.
+use std::{collections::HashMap, fs, io::Error, process, str::FromStr, thread, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::submissions::now;
+use crate::template::{Day, Puzzle, Year};
+
+static FETCH_LOG_FILE_PATH: &str = "./data/fetches.json";
+
+/// Minimum time between two requests to the website.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Number of requests that are kept in the log, older requests are dropped.
+pub const MAX_FETCHES: usize = 100;
+
+/// A single request for a resource of a puzzle, e.g. its input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fetch {
+    pub puzzle: Puzzle,
+    pub resource: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all requests that were made, across all years.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct FetchLog {
+    pub data: Vec<Fetch>,
+}
+
+impl FetchLog {
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(FETCH_LOG_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(FETCH_LOG_FILE_PATH) else {
+            return FetchLog::default();
+        };
+
+        match FetchLog::try_from(s) {
+            Ok(log) => log,
+            Err(e) => {
+                eprintln!("Failed to read \"{FETCH_LOG_FILE_PATH}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Time to wait at `now` before the next request may be made.
+    pub fn wait_time(&self, now: u64) -> Duration {
+        self.data
+            .iter()
+            .map(|f| f.timestamp)
+            .max()
+            .map_or(Duration::ZERO, |last| {
+                let next = last + MIN_REQUEST_INTERVAL.as_secs();
+                Duration::from_secs(next.saturating_sub(now))
+            })
+    }
+
+    /// Runs `fetch` once the minimum request interval has passed and records it.
+    pub fn throttled<T, E>(
+        &mut self,
+        puzzle: Puzzle,
+        resource: &str,
+        fetch: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let wait = self.wait_time(now());
+        if !wait.is_zero() {
+            println!("Waiting {wait:?} before the next request...");
+            thread::sleep(wait);
+        }
+
+        let result = fetch();
+
+        self.data.push(Fetch {
+            puzzle,
+            resource: resource.into(),
+            timestamp: now(),
+        });
+        let excess = self.data.len().saturating_sub(MAX_FETCHES);
+        self.data.drain(..excess);
+
+        if let Err(e) = self.store_file() {
+            eprintln!("Failed to store fetch log: {e}");
+        }
+
+        result
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<FetchLog> for JsonValue {
+    fn from(value: FetchLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for FetchLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(FetchLog {
+            data: json_data
+                .iter()
+                .map(Fetch::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fetch> for JsonValue {
+    fn from(value: &Fetch) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.puzzle.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("resource".into(), JsonValue::String(value.resource.clone()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fetch {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fetch to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected fetch.day to be a Day struct.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|year| Year::new(*year as u16))
+            .ok_or("Expected fetch.year to be a year.")?;
+
+        let resource = json
+            .get("resource")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected fetch.resource to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected fetch.timestamp to be a number.")?;
+
+        Ok(Fetch {
+            puzzle: Puzzle::new(year, day),
+            resource: resource.clone(),
+            timestamp: *timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Fetch, FetchLog};
+    use crate::{day, template::Puzzle};
+
+    fn puzzle(day: crate::template::Day) -> Puzzle {
+        Puzzle::new("2023".parse().unwrap(), day)
+    }
+
+    #[test]
+    fn computes_wait_time() {
+        let log = FetchLog {
+            data: vec![
+                Fetch {
+                    puzzle: puzzle(day!(1)),
+                    resource: "input".into(),
+                    timestamp: 100,
+                },
+                Fetch {
+                    puzzle: puzzle(day!(1)),
+                    resource: "puzzle".into(),
+                    timestamp: 102,
+                },
+            ],
+        };
+
+        assert_eq!(log.wait_time(103), Duration::from_secs(4));
+        assert_eq!(log.wait_time(107), Duration::ZERO);
+        assert_eq!(FetchLog::default().wait_time(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let log = FetchLog {
+            data: vec![Fetch {
+                puzzle: puzzle(day!(3)),
+                resource: "input".into(),
+                timestamp: 1_701_579_600,
+            }],
+        };
+        let json = tinyjson::JsonValue::from(log.clone()).stringify().unwrap();
+        assert_eq!(FetchLog::try_from(json).unwrap().data, log.data);
+    }
+}
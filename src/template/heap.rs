@@ -0,0 +1,203 @@
+//! Counts heap allocations of solution parts with a global allocator, enabled by the `heap-stats` feature.
+//!
+//! Unlike `dhat`, the counts are cheap enough to collect alongside regular runs. They are process-wide,
+//! which is why `--heap` and builds with the `heap-stats` feature cannot be combined with `--jobs`.
+use std::fmt::Display;
+
+/// Whether this build counts allocations. The `dhat-heap` feature brings its own allocator and takes precedence.
+pub const ENABLED: bool = cfg!(all(feature = "heap-stats", not(feature = "dhat-heap")));
+
+/// Allocations of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Largest number of bytes that were allocated at once, on top of what was allocated before the run.
+    pub peak_bytes: u64,
+}
+
+impl Display for HeapStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Runs `f` and counts the allocations it makes, [`None`] if this build does not count allocations.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+    {
+        let start = counting::Snapshot::start();
+        let result = f();
+        (result, Some(start.finish()))
+    }
+
+    #[cfg(not(all(feature = "heap-stats", not(feature = "dhat-heap"))))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::HeapStats;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// Forwards to the system allocator and counts every allocation.
+    struct CountingAlloc;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                // NOTE: like dhat, a reallocation counts as freeing the old block and allocating a new one.
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    /// Counters at the start of a measurement.
+    pub struct Snapshot {
+        allocations: u64,
+        bytes: u64,
+        live_bytes: u64,
+    }
+
+    impl Snapshot {
+        pub fn start() -> Self {
+            let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+            PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+            Self {
+                allocations: ALLOCATIONS.load(Ordering::Relaxed),
+                bytes: BYTES.load(Ordering::Relaxed),
+                live_bytes,
+            }
+        }
+
+        pub fn finish(self) -> HeapStats {
+            HeapStats {
+                allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+                bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+                peak_bytes: PEAK_BYTES
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(self.live_bytes),
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, HeapStats, ENABLED};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+
+        let stats = HeapStats {
+            allocations: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 2.0 KiB allocated, 1.0 KiB peak"
+        );
+    }
+
+    #[test]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| {
+            let v: Vec<u64> = Vec::with_capacity(1024);
+            let w: Vec<u8> = Vec::with_capacity(4096);
+            v.capacity() + w.capacity()
+        });
+        assert_eq!(len, 5120);
+
+        if ENABLED {
+            // NOTE: the test harness may allocate on other threads, so the counts are lower bounds.
+            let stats = stats.unwrap();
+            assert!(stats.allocations >= 2);
+            assert!(stats.bytes >= 8 * 1024 + 4096);
+            assert!(stats.peak_bytes >= 8 * 1024 + 4096);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+}
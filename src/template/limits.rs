@@ -0,0 +1,120 @@
+//! Limits for solutions that run in a child process, so a single day can not block a run of many days.
+use std::{env, process::Command, time::Duration};
+
+use crate::template::parse_secs;
+
+const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+const MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+
+/// Resource limits of a single solution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time after which the solution is killed.
+    pub timeout: Option<Duration>,
+    /// Maximum size of the address space of the solution in bytes. Only supported on Linux.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    /// Reads the limits from the `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT` environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        let mut limits = Self::default();
+
+        if let Ok(x) = env::var(TIMEOUT_ENV) {
+            limits.timeout = Some(parse_secs(&x)?);
+        }
+        if let Ok(x) = env::var(MEMORY_LIMIT_ENV) {
+            limits.memory = Some(parse_mebibytes(&x)?);
+        }
+
+        limits.validate()
+    }
+
+    /// Returns the limits if they are supported on this platform, an error otherwise.
+    pub fn validate(self) -> Result<Self, String> {
+        if self.timeout.is_some_and(|t| t.is_zero()) {
+            return Err("expecting a timeout larger than zero.".into());
+        }
+        if self.memory.is_some() && !cfg!(target_os = "linux") {
+            return Err("memory limits are only supported on Linux.".into());
+        }
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+
+    /// Limits the address space of the process spawned by `command`.
+    /// The timeout is not applied here, it is up to the caller to kill the process.
+    pub fn apply(&self, command: &mut Command) {
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = self.memory {
+            use std::os::unix::process::CommandExt;
+
+            let bytes = libc::rlim_t::try_from(bytes).unwrap_or(libc::RLIM_INFINITY);
+
+            // SAFETY: `setrlimit` is async-signal-safe and only changes the limits of the child process.
+            unsafe {
+                command.pre_exec(move || {
+                    let limit = libc::rlimit {
+                        rlim_cur: bytes,
+                        rlim_max: bytes,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                        Ok(())
+                    } else {
+                        Err(std::io::Error::last_os_error())
+                    }
+                });
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = command;
+    }
+}
+
+/// Parses a (fractional) number of mebibytes into bytes, e.g. `512` or `1.5`.
+pub fn parse_mebibytes(s: &str) -> Result<u64, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x > 0.0)
+        .map(|x| (x * 1024.0 * 1024.0) as u64)
+        .ok_or(format!("expecting a positive number of MiB, got `{s}`."))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_mebibytes, Limits};
+
+    #[test]
+    fn parses_mebibytes() {
+        assert_eq!(parse_mebibytes("1"), Ok(1024 * 1024));
+        assert_eq!(parse_mebibytes("0.5"), Ok(512 * 1024));
+        assert!(parse_mebibytes("0").is_err());
+        assert!(parse_mebibytes("-1").is_err());
+        assert!(parse_mebibytes("1GB").is_err());
+    }
+
+    #[test]
+    fn validates_limits() {
+        let limits = Limits {
+            timeout: Some(Duration::ZERO),
+            memory: None,
+        };
+        assert!(limits.validate().is_err());
+
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(10)),
+            memory: None,
+        };
+        assert!(!limits.is_empty());
+        assert!(limits.validate().is_ok());
+        assert!(Limits::default().is_empty());
+    }
+}
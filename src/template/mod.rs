@@ -1,44 +1,71 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
 pub mod runner;
 
+pub use bench_config::*;
 pub use day::*;
+pub use heap::ENABLED as HEAP_STATS_ENABLED;
+pub use limits::*;
+pub use params::*;
+pub use run_multi::MultiRunOptions;
+pub use year::*;
 
+mod answers;
+mod bench_config;
 mod day;
+mod description;
+mod example_tables;
+mod fetch_log;
+mod heap;
+mod limits;
+mod output;
+mod params;
+mod protocol;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod stats;
+mod submissions;
+mod templates;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string. E.g. like `data/2023/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "", "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `data/2023/examples/01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, &format!("-{part}"), "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY`, `YEAR` and `PUZZLE` and sets up the input and runner for each part.
 ///
+/// The year is read from the name of the solution file, which is expected to be `<year>_<day>.rs`.
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse` after the day, the input is parsed once by `parse(&str) -> Result<T, E>` and both parts
+/// are passed a reference to the parsed value, e.g. `solution!(5, parse)` or `solution!(5, parse, 1)`.
+/// The time it takes to parse the input is reported separately from the parts.
+///
+/// Parts may take a [`Params`] reference after their input to receive parameters that differ between examples and the real input.
+/// Every example in `data/<year>/examples/<day>.toml` becomes a test of the solution, see [`examples`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +77,91 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse) => {
+        $crate::solution!(@impl_parsed $day, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl_parsed $day, [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl_parsed $day, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, |input: &str, options: &$crate::template::runner::RunOptions| {
+            use $crate::template::runner::*;
+            let mut parts = vec![];
+            $(
+                if options.runs_part($part) {
+                    parts.push(run_part($func, input, PUZZLE, $part, options));
+                }
+            )*
+            Records { parse: None, parts }
+        });
+    };
+
+    (@impl_parsed $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, |input: &str, options: &$crate::template::runner::RunOptions| {
+            use $crate::template::runner::*;
+            let (parsed, parse_record) = run_parse(parse, input, PUZZLE, options);
+            let mut parts = vec![];
+            $(
+                if options.runs_part($part) {
+                    parts.push(match &parsed {
+                        Ok(parsed) => run_part($func, parsed, PUZZLE, $part, options),
+                        Err(error) => skip_part(PUZZLE, $part, error, options),
+                    });
+                }
+            )*
+            Records {
+                parse: Some(parse_record),
+                parts,
+            }
+        });
+    };
+
+    (@common $day:expr, $run:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current puzzle.
+        const YEAR: $crate::template::Year = $crate::template::Year::from_solution_path(file!());
+
+        /// The current puzzle, used to locate its data files.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle { year: YEAR, day: DAY };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// The solution's entry point, used to run it in-process.
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            puzzle: PUZZLE,
+            run: $run,
+        };
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_args();
+            if options.examples {
+                $crate::template::examples::run_examples(&SOLUTION);
+                return;
+            }
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            let records = (SOLUTION.run)(&input, &options);
+            exit_on_failure(&records.parts);
+        }
+
+        /// Tests generated by `build.rs`, one for each example of the day.
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(dead_code)]
+            fn test_example(index: usize) {
+                $crate::template::examples::test_example(&super::SOLUTION, index);
+            }
+
+            // NOTE: the generated file mirrors the path of the solution, which is the only literal that identifies it.
+            include!(concat!(env!("OUT_DIR"), "/examples/", file!()));
         }
     };
 }
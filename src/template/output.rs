@@ -0,0 +1,87 @@
+//! Output of the runner that can be captured per thread.
+//! This allows running several days concurrently while printing their output as contiguous blocks.
+use std::{
+    cell::RefCell,
+    fmt::{self, Write as _},
+    io::{stdout, Write as _},
+};
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, collecting everything written through [`out!`] and [`outln!`] on the current thread
+/// instead of printing it to stdout.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let previous = CAPTURED.replace(Some(String::new()));
+    let result = f();
+    let captured = CAPTURED.replace(previous).unwrap_or_default();
+    (result, captured)
+}
+
+/// Returns `true` if output on the current thread is being captured.
+pub fn is_capturing() -> bool {
+    CAPTURED.with_borrow(Option::is_some)
+}
+
+pub fn write_fmt(args: fmt::Arguments) {
+    CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(buffer) => {
+            let _ = buffer.write_fmt(args);
+        }
+        None => {
+            let mut stdout = stdout();
+            let _ = stdout.write_fmt(args);
+            let _ = stdout.flush();
+        }
+    });
+}
+
+/// Like [`print!`], but respects [`capture`].
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write_fmt(format_args!($($arg)*))
+    };
+}
+
+/// Like [`println!`], but respects [`capture`].
+macro_rules! outln {
+    () => {
+        $crate::template::output::write_fmt(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::write_fmt(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use out;
+pub(crate) use outln;
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, is_capturing, out, outln};
+
+    #[test]
+    fn captures_output() {
+        let (result, output) = capture(|| {
+            out!("Part {}: ", 1);
+            outln!("{}", 42);
+            is_capturing()
+        });
+        assert!(result);
+        assert_eq!(output, "Part 1: 42\n");
+        assert!(!is_capturing());
+    }
+
+    #[test]
+    fn captures_nested_output() {
+        let (inner, outer) = capture(|| {
+            outln!("outer");
+            capture(|| outln!("inner")).1
+        });
+        assert_eq!(inner, "inner\n");
+        assert_eq!(outer, "outer\n");
+    }
+}
@@ -0,0 +1,148 @@
+//! Parameters that differ between the examples and the real input of a puzzle, e.g. a number of steps.
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// Parameters of a solution, taken from `--param key=value` or from the `[example.params]` of an example.
+/// Parts receive them if they take a `&Params` after their input, and fall back to defaults for parameters that are not set:
+/// ```ignore
+/// pub fn part_two(input: &str, params: &Params) -> Result<usize, String> {
+///     let factor = params.get_or("factor", 1_000_000)?;
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Sets parameter `key`, returns its previous value.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.0.insert(key.into(), value.into())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the parameters, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns parameter `key` parsed as `T`, [`None`] if it is not set.
+    /// Fails if the parameter can not be parsed as `T`, which usually is a typo in the examples or on the command line.
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self.0.get(key) else {
+            return Ok(None);
+        };
+        value
+            .parse()
+            .map(Some)
+            .map_err(|e| format!("invalid value {value:?} for parameter `{key}`: {e}"))
+    }
+
+    /// Returns parameter `key` parsed as `T`, `default` if it is not set. Fails like [`Params::get`].
+    pub fn get_or<T>(&self, key: &str, default: T) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    /// Converts the parameters into `--param key=value` arguments of a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|(key, value)| ["--param".into(), format!("{key}={value}")])
+            .collect()
+    }
+
+    /// Reads all `--param key=value` arguments.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut params = Self::new();
+        for pair in args.windows(2).filter(|pair| pair[0] == "--param") {
+            let (key, value) = parse_param(&pair[1])?;
+            params.insert(key, value);
+        }
+        Ok(params)
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self.0.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Parses a `key=value` pair of a `--param` argument.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expecting a parameter as `key=value`, got `{s}`.")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_param, Params};
+
+    #[test]
+    fn gets_typed_values() {
+        let params: Params = [("factor", "10"), ("scale", "0.5"), ("name", "x")]
+            .into_iter()
+            .collect();
+
+        assert_eq!(params.get::<u64>("factor"), Ok(Some(10)));
+        assert_eq!(params.get::<f64>("scale"), Ok(Some(0.5)));
+        assert_eq!(params.get::<String>("name"), Ok(Some("x".into())));
+        assert_eq!(params.get_or("steps", 64_usize), Ok(64));
+        assert_eq!(params.to_string(), "factor=10, name=x, scale=0.5");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let params: Params = [("name", "x")].into_iter().collect();
+        assert!(params
+            .get::<u32>("name")
+            .unwrap_err()
+            .contains("parameter `name`"));
+        assert!(params.get_or("name", 1_u32).is_err());
+    }
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(parse_param("factor=10"), Ok(("factor".into(), "10".into())));
+        assert_eq!(parse_param("a=b=c"), Ok(("a".into(), "b=c".into())));
+        assert!(parse_param("factor").is_err());
+        assert!(parse_param("=10").is_err());
+
+        let args: Vec<String> = ["2023_11", "--param", "factor=10", "--param", "steps=6"]
+            .map(String::from)
+            .to_vec();
+        let params = Params::from_args(&args).unwrap();
+        assert_eq!(params.get::<u32>("steps"), Ok(Some(6)));
+        assert_eq!(Params::from_args(&params.to_args()[..]), Ok(params));
+    }
+}
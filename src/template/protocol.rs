@@ -0,0 +1,410 @@
+//! Machine-readable records that solution binaries emit when invoked with `--json`.
+//!
+//! Every record is a single line of JSON on stdout. `run_multi` consumes these records instead of
+//! parsing the human-readable output, so answers or debug prints can not interfere with timings.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::heap::HeapStats;
+use crate::template::stats::Stats;
+use crate::template::Day;
+
+/// Flag that makes a solution binary emit records.
+pub const JSON_ARG: &str = "--json";
+
+/// Exit code of a solution binary if one of its parts failed with an error.
+pub const PART_FAILED_EXIT_CODE: i32 = 1;
+
+static RECORD_TYPE: &str = "part_result";
+
+static PARSE_RECORD_TYPE: &str = "parse_result";
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Error message of a part that failed. Parts without an answer and without an error are unsolved.
+    pub error: Option<String>,
+    /// Execution time of the part, i.e. the mean of all samples when benched.
+    pub nanos: u128,
+    pub samples: u128,
+    pub stats: Option<Stats>,
+    /// Allocations of the first run of the part, if the solution was built with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
+}
+
+/// The outcome of parsing the input of a solution that parses its input once for both parts.
+/// Parse errors are part of the [`PartRecord`]s of the parts that could not run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseRecord {
+    pub day: Day,
+    /// Execution time of the parser, i.e. the mean of all samples when benched.
+    pub nanos: u128,
+    pub samples: u128,
+    pub stats: Option<Stats>,
+    pub heap: Option<HeapStats>,
+}
+
+/// All records of a single run of a solution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Records {
+    /// Present if the solution parses its input separately from its parts.
+    pub parse: Option<ParseRecord>,
+    pub parts: Vec<PartRecord>,
+}
+
+impl PartRecord {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(u64::try_from(self.nanos).unwrap_or(u64::MAX))
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: stringify only fails for non-finite numbers, which records do not contain.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Parses a line of output, returns [`None`] if it is not a record.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json = JsonValue::from_str(line).ok()?;
+        PartRecord::try_from(&json).ok()
+    }
+}
+
+impl ParseRecord {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(u64::try_from(self.nanos).unwrap_or(u64::MAX))
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: stringify only fails for non-finite numbers, which records do not contain.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Parses a line of output, returns [`None`] if it is not a parse record.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json = JsonValue::from_str(line).ok()?;
+        ParseRecord::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("type".into(), JsonValue::String(RECORD_TYPE.into()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        if json.get("type").and_then(|v| v.get::<String>()) != Some(&RECORD_TYPE.to_string()) {
+            return Err(format!("Expected record.type to be `{RECORD_TYPE}`."));
+        }
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected record.error to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        // NOTE: heap statistics are optional, they are only collected with the `heap-stats` feature.
+        let heap = match json.get("heap") {
+            Some(v) if !v.is_null() => Some(HeapStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartRecord {
+            day,
+            part: number("part")? as u8,
+            answer: answer.cloned(),
+            error,
+            nanos: number("nanos")? as u128,
+            samples: number("samples")? as u128,
+            stats,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&ParseRecord> for JsonValue {
+    fn from(value: &ParseRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("type".into(), JsonValue::String(PARSE_RECORD_TYPE.into()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ParseRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        if json.get("type").and_then(|v| v.get::<String>()) != Some(&PARSE_RECORD_TYPE.to_string())
+        {
+            return Err(format!("Expected record.type to be `{PARSE_RECORD_TYPE}`."));
+        }
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let heap = match json.get("heap") {
+            Some(v) if !v.is_null() => Some(HeapStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(ParseRecord {
+            day,
+            nanos: number("nanos")? as u128,
+            samples: number("samples")? as u128,
+            stats,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected heap stats.{key} to be a number."))
+        };
+
+        Ok(HeapStats {
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParseRecord, PartRecord};
+    use crate::day;
+    use crate::template::heap::HeapStats;
+
+    fn get_mock_record() -> PartRecord {
+        PartRecord {
+            day: day!(3),
+            part: 2,
+            answer: Some("a\nmulti-line (1ms @ 10 samples)\nanswer".into()),
+            error: None,
+            nanos: 74_130,
+            samples: 100,
+            stats: None,
+            heap: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = get_mock_record();
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let line = r#"{ "type": "part_result", "day": "01", "part": 1, "answer": null, "nanos": 10, "samples": 1, "stats": null }"#;
+        let record = PartRecord::from_json_line(line).unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error, None);
+        assert_eq!(record.nanos, 10);
+    }
+
+    #[test]
+    fn roundtrips_heap_stats() {
+        let record = PartRecord {
+            heap: Some(HeapStats {
+                allocations: 12,
+                bytes: 4_096,
+                peak_bytes: 1_024,
+            }),
+            ..get_mock_record()
+        };
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let record = PartRecord {
+            answer: None,
+            error: Some("invalid tile: 'x'".into()),
+            ..get_mock_record()
+        };
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn distinguishes_parse_records() {
+        let record = ParseRecord {
+            day: day!(3),
+            nanos: 1_200,
+            samples: 1,
+            stats: None,
+            heap: None,
+        };
+        let line = record.to_json_line();
+        assert_eq!(ParseRecord::from_json_line(&line), Some(record));
+        assert_eq!(PartRecord::from_json_line(&line), None);
+        assert_eq!(
+            ParseRecord::from_json_line(&get_mock_record().to_json_line()),
+            None
+        );
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(
+            PartRecord::from_json_line("Part 1: 42 (1ms @ 10 samples)"),
+            None
+        );
+        assert_eq!(PartRecord::from_json_line(r#"{ "part": 1 }"#), None);
+        assert_eq!(PartRecord::from_json_line("{ not json"), None);
+    }
+}
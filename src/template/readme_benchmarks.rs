@@ -1,12 +1,18 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Puzzle, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marker of a table that only holds the benchmarks of a single year.
+/// Used instead of [`MARKER`] if present, so a readme can hold tables for several years.
+fn year_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -19,18 +25,23 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub struct TablePosition {
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table enclosed by a pair of `marker`s. A single marker is treated as an empty table.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,47 +62,98 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Header of a column and the value of a timing in it.
+type Column = (&'static str, fn(&Timing) -> Option<String>);
+
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let header = if marker == MARKER {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} Benchmarks ({year})")
+    };
+
+    // NOTE: optional columns are only shown if any solution has a value for them.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_heap = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_heap.is_some() || t.part_2_heap.is_some() || t.parse_heap.is_some());
+
+    let mut columns: Vec<Column> = vec![];
+    if has_parse {
+        columns.push(("Parse", |t| t.parse.clone()));
+    }
+    columns.push(("Part 1", |t| t.part_1.clone()));
+    columns.push(("Part 2", |t| t.part_2.clone()));
+    if has_heap && has_parse {
+        columns.push(("Parse heap", |t| t.parse_heap.map(|h| h.to_string())));
+    }
+    if has_heap {
+        columns.push(("Part 1 heap", |t| t.part_1_heap.map(|h| h.to_string())));
+        columns.push(("Part 2 heap", |t| t.part_2_heap.map(|h| h.to_string())));
+    }
+
+    let headers: Vec<&str> = columns.iter().map(|(header, _)| *header).collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", headers.join(" | ")),
+        format!("| :---: |{} :---:  |", " :---: |".repeat(columns.len() - 1)),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = Puzzle::new(year, timing.day).bin_path();
+        let cells: String = columns
+            .iter()
+            .map(|(_, value)| format!(" `{}` |", value(&timing).unwrap_or_else(|| "-".into())))
+            .collect();
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let year_marker = year_marker(year);
+    let marker = if s.contains(&year_marker) {
+        year_marker.as_str()
+    } else {
+        MARKER
+    };
+
+    let positions = locate_table(s, marker)?;
+    let table = construct_table("##", marker, year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -99,7 +161,14 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::heap::HeapStats, template::timings::Timing, template::timings::Timings,
+        template::Year,
+    };
+
+    fn year() -> Year {
+        "2023".parse().unwrap()
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -108,21 +177,43 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 9e+10,
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -130,28 +221,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +250,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,9 +259,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +270,57 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_parse_times() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, year(), timings, 195.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2023_01.rs) | `-` | `10ms` | `20ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2023_02.rs) | `5ms` | `30ms` | `40ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_heap_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_heap = Some(HeapStats {
+            allocations: 2,
+            bytes: 2048,
+            peak_bytes: 1024,
+        });
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, year(), timings, 190.0).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Part 1 heap | Part 2 heap |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| :---: | :---: | :---: | :---: | :---:  |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| `10ms` | `20ms` | `2 allocations, 2.0 KiB allocated, 1.0 KiB peak` | `-` |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn prefers_year_markers() {
+        let year_marker = "<!--- benchmarking table 2023 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{year_marker}{year_marker}");
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n")));
+        assert!(s.contains("## Benchmarks (2023)"));
+        assert!(s.contains("./src/bin/2023_04.rs"));
+    }
 }
@@ -0,0 +1,160 @@
+//! Module that updates the readme with the stars earned for each day.
+//! Replaces the table that `advent-readme-stars` writes, but uses the locally recorded answers.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{Puzzle, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Marker of a table that only holds the stars of a single year.
+/// Used instead of [`MARKER`] if present, so a readme can hold tables for several years.
+fn year_marker(year: Year) -> String {
+    format!("<!--- advent_readme_stars table {year} --->")
+}
+
+static STAR: &str = "⭐";
+
+fn construct_table(prefix: &str, marker: &str, year: Year, answers: &Answers) -> String {
+    let mut lines: Vec<String> = vec![
+        marker.into(),
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---:  |".into(),
+    ];
+
+    let mut total = 0;
+
+    for answer in &answers.data {
+        let stars: Vec<&str> = [1, 2]
+            .iter()
+            .map(|part| answer.get(*part).map_or(" ", |_| STAR))
+            .collect();
+
+        if stars.iter().all(|s| *s != STAR) {
+            continue;
+        }
+
+        total += stars.iter().filter(|s| **s == STAR).count();
+
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            answer.day.into_inner(),
+            Puzzle::new(year, answer.day).bin_path(),
+            stars[0],
+            stars[1]
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} {STAR}**"));
+    lines.push(marker.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Year, answers: &Answers) -> Result<(), Error> {
+    let year_marker = year_marker(year);
+    let marker = if s.contains(&year_marker) {
+        year_marker.as_str()
+    } else {
+        MARKER
+    };
+
+    let positions = locate_table(s, marker)?;
+    let table = construct_table("##", marker, year, answers);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: Year, answers: &Answers) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, answers)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, year_marker, MARKER};
+    use crate::{
+        day,
+        template::answers::{Answer, Answers},
+        template::Year,
+    };
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("142".into()),
+                    part_2: Some("281".into()),
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: Some("8".into()),
+                    part_2: None,
+                },
+                Answer {
+                    day: day!(3),
+                    part_1: None,
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, Year::new(2023).unwrap(), &get_mock_answers()).unwrap();
+    }
+
+    #[test]
+    fn updates_single_marker() {
+        let mut s = format!("foo\n{MARKER}\nbar");
+        let year = Year::new(2023).unwrap();
+        update_content(&mut s, year, &get_mock_answers()).unwrap();
+        update_content(&mut s, year, &get_mock_answers()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+    }
+
+    #[test]
+    fn prefers_year_markers() {
+        let year = Year::new(2023).unwrap();
+        let other = Year::new(2022).unwrap();
+        let mut s = format!("{MARKER}\n{}\n{}", year_marker(other), year_marker(year));
+        update_content(&mut s, year, &get_mock_answers()).unwrap();
+        assert_eq!(s.matches(&year_marker(year)).count(), 2);
+        assert!(s.starts_with(&format!("{MARKER}\n{}\n", year_marker(other))));
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\nbar");
+        update_content(&mut s, Year::new(2023).unwrap(), &get_mock_answers()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023_01.rs) | ⭐ | ⭐ |",
+            "| [Day 2](./src/bin/2023_02.rs) | ⭐ |   |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
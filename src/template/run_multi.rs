@@ -1,48 +1,299 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    output::{self, out, outln},
+    protocol::{PartRecord, Records},
+    read_file,
+    runner::{RunOptions, Solution},
+    BenchConfig, Day, Limits, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
-    all_days,
+    all_days, heap,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// The outcome of [`run_multi`].
+pub struct MultiRun {
+    /// Records of all parts that were run, in order of days.
+    pub records: Vec<PartRecord>,
+    /// Timings of all days, present if solutions were benched.
+    pub timings: Option<Timings>,
+    /// Days whose solution did not finish, e.g. because it panicked.
+    pub failures: Vec<(Day, Failure)>,
+}
+
+impl MultiRun {
+    /// Whether a part failed with an error or a solution did not finish.
+    /// Parts that are merely unsolved do not count as failures.
+    pub fn has_failures(&self) -> bool {
+        !self.failures.is_empty() || self.records.iter().any(|r| r.error.is_some())
+    }
+}
 
-    let mut need_space = false;
+/// Why the solution of a day did not finish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Failure {
+    /// The solution panicked, failed to build or exited unexpectedly.
+    Crashed,
+    /// The solution was killed after exceeding its timeout.
+    TimedOut,
+    /// The solution exceeded its memory limit.
+    OutOfMemory,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Crashed => write!(f, "crashed"),
+            Failure::TimedOut => write!(f, "timed out"),
+            Failure::OutOfMemory => write!(f, "out of memory"),
+        }
+    }
+}
+
+/// The outcome of running the solution of a single day.
+#[derive(Default)]
+pub struct DayRun {
+    pub records: Records,
+    /// Present if the solution did not finish all of its parts.
+    pub failure: Option<Failure>,
+}
+
+/// How [`run_multi`] runs the solutions of several days.
+#[derive(Clone)]
+pub struct MultiRunOptions<'a> {
+    /// Run release builds of the solutions.
+    pub is_release: bool,
+    /// Bench the solutions with this budget.
+    pub bench_config: Option<BenchConfig>,
+    /// Number of days that run concurrently.
+    pub jobs: usize,
+    /// Count the heap allocations of every part.
+    pub heap_stats: bool,
+    /// Limits of spawned solutions.
+    pub limits: Limits,
+    /// Solutions that can run in-process.
+    pub solutions: &'a [Solution],
+}
+
+/// Runs the solutions for the given days of a year. Solutions are benched if a `bench_config` is set.
+///
+/// Days that are part of `solutions` are run in-process if the current build matches the requested profile.
+/// All other days are run by spawning their binary via `cargo run`.
+///
+/// With `jobs` larger than one, days run concurrently. Their output is buffered and printed in order of days.
+/// Timed runs always run serially to keep the measurements trustworthy.
+///
+/// With `heap_stats`, allocations are counted. If the current build does not count allocations,
+/// all days are spawned with the `heap-stats` feature instead of running in-process.
+///
+/// Days are also spawned if `limits` are set, as a solution can only be stopped if it runs in its own process.
+pub fn run_multi(year: Year, days_to_run: &HashSet<Day>, options: &MultiRunOptions) -> MultiRun {
+    let MultiRunOptions {
+        is_release,
+        bench_config,
+        jobs,
+        heap_stats,
+        limits,
+        solutions,
+    } = options;
+    let bench_config = bench_config.as_ref();
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let run_day = |day: Day| {
+        let puzzle = Puzzle::new(year, day);
+        let solution = solutions.iter().find(|s| s.puzzle == puzzle);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        // NOTE: a missing input leaves the day unsolved, it is not a failure of the solution.
+        let input_path = puzzle.data_path("inputs", "", "txt");
+        if Path::new(&puzzle.bin_path()).exists() && !input_path.exists() {
+            outln!("No input file at `{}`.", input_path.display());
+            return DayRun::default();
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        let is_same_build = *is_release == cfg!(not(debug_assertions))
+            && (!heap_stats || heap::ENABLED)
+            && limits.is_empty();
+
+        match solution {
+            Some(solution) if is_same_build => run_in_process(solution, bench_config),
+            _ => child_commands::run_solution(puzzle, options).unwrap_or_else(|e| {
+                outln!("Failed to run the solution: {e}");
+                DayRun {
+                    records: Records::default(),
+                    failure: Some(Failure::Crashed),
+                }
+            }),
+        }
+    };
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut all_records: Vec<PartRecord> = vec![];
+    let mut failures: Vec<(Day, Failure)> = vec![];
+
+    let mut finish_day = |day: Day, run: DayRun| {
+        if let Some(failure) = run.failure {
+            match (failure, limits.timeout) {
+                (Failure::TimedOut, Some(timeout)) => {
+                    outln!("Failed, the solution timed out after {timeout:?}.");
+                }
+                (Failure::OutOfMemory, _) => outln!("Failed, the solution ran out of memory."),
+                _ => outln!("Failed, the solution {failure}."),
             }
+            failures.push((day, failure));
+        } else if run.records.parts.is_empty() {
+            outln!("Not solved.");
+        }
+
+        if !run.records.parts.is_empty() {
+            timings.push(child_commands::collect_timing(&run.records, day));
+            all_records.extend(run.records.parts);
+        }
+    };
+
+    if bench_config.is_some() || *jobs <= 1 {
+        for (i, &day) in days.iter().enumerate() {
+            print_header(day, i > 0);
+            let run = run_day(day);
+            finish_day(day, run);
+        }
+    } else {
+        let run_captured = |day: Day| output::capture(|| run_day(day));
+
+        run_parallel(&days, *jobs, run_captured, |i, day, (run, output)| {
+            print_header(day, i > 0);
+            out!("{output}");
+            finish_day(day, run);
         });
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    print_summary(&all_records, &failures);
+
+    let timings = bench_config.map(|_| {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
-        println!(
+        outln!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    MultiRun {
+        records: all_records,
+        timings,
+        failures,
+    }
+}
+
+/// Prints how many parts were solved, unsolved or failed, and how many days did not finish.
+fn print_summary(records: &[PartRecord], failures: &[(Day, Failure)]) {
+    let solved = records.iter().filter(|r| r.answer.is_some()).count();
+    let failed = records.iter().filter(|r| r.error.is_some()).count();
+    let unsolved = records.len() - solved - failed;
+
+    outln!();
+    out!("{ANSI_BOLD}Summary:{ANSI_RESET} {solved} solved, {unsolved} unsolved, {failed} failed part(s)");
+
+    let mut counts: BTreeMap<Failure, usize> = BTreeMap::new();
+    for (_, failure) in failures {
+        *counts.entry(*failure).or_default() += 1;
+    }
+
+    for (failure, count) in counts {
+        out!(", {count} {failure} day(s)");
+    }
+    outln!(".");
+}
+
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        outln!();
+    }
+    outln!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    outln!("------");
+}
+
+/// Runs `run` for every day on up to `jobs` threads.
+/// `on_result` is called on the current thread in order of `days`, as soon as a result and all results before it are available.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut on_result: impl FnMut(usize, Day, T),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (run, next_day) = (&run, &next_day);
+
+            scope.spawn(move || loop {
+                let i = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                if tx.send((i, run(day))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut pending: BTreeMap<usize, T> = BTreeMap::new();
+        let mut next_result = 0;
+
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_result) {
+                on_result(next_result, days[next_result], result);
+                next_result += 1;
+            }
+        }
+    });
+}
+
+/// Run a solution in the current process, guarding against panics in the solution.
+fn run_in_process(solution: &Solution, bench_config: Option<&BenchConfig>) -> DayRun {
+    let options = RunOptions {
+        bench_config: bench_config.cloned(),
+        ..RunOptions::default()
+    };
+
+    let run = panic::catch_unwind(AssertUnwindSafe(|| {
+        let input = read_file("inputs", solution.puzzle);
+        (solution.run)(&input, &options)
+    }));
+
+    // NOTE: the panic message was already printed by the panic hook.
+    match run {
+        Ok(records) => DayRun {
+            records,
+            failure: None,
+        },
+        Err(_) => DayRun {
+            records: Records::default(),
+            failure: Some(Failure::Crashed),
+        },
     }
 }
 
@@ -58,199 +309,412 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture child process output."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{heap, DayRun, Error, Failure};
+    use crate::template::{
+        output::{self, outln},
+        protocol::{ParseRecord, PartRecord, Records, JSON_ARG, PART_FAILED_EXIT_CODE},
+        Day, MultiRunOptions, Puzzle,
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    use tinyjson::JsonValue;
+
+    /// Run the solution bin for a given puzzle.
+    ///
+    /// The binary is built first and then run directly, so that the `limits` apply to the solution only.
+    /// A solution that exceeds its timeout is killed, the records of parts that finished before are kept.
+    /// The `jobs` and `solutions` of the options are not used.
+    pub fn run_solution(puzzle: Puzzle, options: &MultiRunOptions) -> Result<DayRun, Error> {
+        let limits = &options.limits;
+        // NOTE: a build that counts allocations makes spawned solutions count them, too.
+        let heap_stats = options.heap_stats || heap::ENABLED;
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(DayRun::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let Some(executable) = build_solution(puzzle, options.is_release, heap_stats)? else {
+            return Ok(DayRun {
+                records: Records::default(),
+                failure: Some(Failure::Crashed),
+            });
+        };
+
+        // request machine-readable records alongside the regular output.
+        let mut args: Vec<String> = vec![JSON_ARG.into()];
 
-        if is_release {
-            args.push("--release");
+        if let Some(bench_config) = &options.bench_config {
+            // mirror `--time` flag and benchmark settings to child invocations.
+            args.push("--time".into());
+            args.extend(bench_config.to_args());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let mut command = Command::new(executable);
+        command
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        limits.apply(&mut command);
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing records from stdout.
+        // when output is captured, stderr is collected and appended to the captured output.
+        let is_capturing = output::is_capturing();
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let mut records = Records::default();
+
+        let stderr_thread = thread::spawn(move || {
+            let mut lines = vec![];
+            let mut is_out_of_memory = false;
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                // NOTE: this is how the default allocation error handler reports a failed allocation.
+                is_out_of_memory |= line.starts_with("memory allocation of");
+                if is_capturing {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            (lines, is_out_of_memory)
+        });
+
+        // NOTE: stdout is read on a separate thread, so the timeout can be checked while waiting for output.
+        let (tx, rx) = mpsc::channel();
+        let stdout_thread = thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+
+        let mut handle_line = |line: String| {
+            if let Some(record) = PartRecord::from_json_line(&line) {
+                if record.day == puzzle.day {
+                    records.parts.push(record);
+                    return;
+                }
+            } else if let Some(record) = ParseRecord::from_json_line(&line) {
+                if record.day == puzzle.day {
+                    records.parse = Some(record);
+                    return;
+                }
+            }
+            outln!("{line}");
+        };
+
+        let mut is_timed_out = false;
+
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => handle_line(line),
+                Err(RecvTimeoutError::Timeout) => {
+                    is_timed_out = true;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        // the solution may still run after closing its output, so the deadline applies to waiting as well.
+        let status = loop {
+            if is_timed_out {
+                cmd.kill()?;
+                break cmd.wait()?;
+            }
+            if let Some(status) = cmd.try_wait()? {
+                break status;
+            }
+            is_timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if !is_timed_out {
+                thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        stdout_thread.join().unwrap();
+        rx.try_iter().for_each(&mut handle_line);
 
-        Ok(output)
+        let (stderr_lines, is_out_of_memory) = stderr_thread.join().unwrap();
+        for line in stderr_lines {
+            outln!("{line}");
+        }
+
+        // NOTE: solutions exit with a dedicated code if a part failed, the failure is already part of its record.
+        let has_errors = records.parts.iter().any(|r| r.error.is_some());
+        let crashed =
+            !status.success() && (status.code() != Some(PART_FAILED_EXIT_CODE) || !has_errors);
+
+        let failure = if is_timed_out {
+            Some(Failure::TimedOut)
+        } else if crashed && is_out_of_memory && limits.memory.is_some() {
+            Some(Failure::OutOfMemory)
+        } else if crashed {
+            Some(Failure::Crashed)
+        } else {
+            None
+        };
+
+        Ok(DayRun { records, failure })
+    }
+
+    /// Builds the binary of a solution, returns the path of its executable or [`None`] if the build failed.
+    /// Build errors are forwarded like the output of the solution.
+    fn build_solution(
+        puzzle: Puzzle,
+        is_release: bool,
+        heap_stats: bool,
+    ) -> Result<Option<PathBuf>, Error> {
+        let name = puzzle.to_string();
+
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            name.clone(),
+            "--message-format=json-render-diagnostics".into(),
+        ];
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        if heap_stats {
+            args.push("--features".into());
+            args.push("heap-stats".into());
+        }
+
+        let output = Command::new("cargo").args(&args).output()?;
+
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            if output::is_capturing() {
+                outln!("{line}");
+            } else {
+                eprintln!("{line}");
+            }
+        }
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let executable = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| artifact_executable(line, &name));
+
+        Ok(executable)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
+    /// Reads the path of the executable of binary `name` from a line of cargo's JSON output.
+    fn artifact_executable(line: &str, name: &str) -> Option<PathBuf> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        let reason = message.get("reason")?.get::<String>()?;
+        let target = message.get("target")?.get::<HashMap<String, JsonValue>>()?;
+        let target_name = target.get("name")?.get::<String>()?;
+
+        if reason != "compiler-artifact" || target_name != name {
+            return None;
+        }
+
+        message
+            .get("executable")?
+            .get::<String>()
+            .map(PathBuf::from)
+    }
+
+    /// Collects the timing of a day from the records emitted by its solution.
+    /// Only parts that were benched contribute to the timing, as does the parser if it was benched.
+    pub fn collect_timing(records: &Records, day: Day) -> super::Timing {
+        let mut timing = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            parse_heap: None,
             total_nanos: 0_f64,
         };
 
-        output
+        if let Some(record) = records.parse.as_ref().filter(|r| r.stats.is_some()) {
+            timing.parse = Some(format!("{:.1?}", record.duration()));
+            timing.parse_stats.clone_from(&record.stats);
+            timing.parse_heap = record.heap;
+            timing.total_nanos += record.nanos as f64;
+        }
+
+        for record in records
+            .parts
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.stats.is_some() && r.error.is_none())
+        {
+            let duration_str = Some(format!("{:.1?}", record.duration()));
+
+            match record.part {
+                1 => {
+                    timing.part_1 = duration_str;
+                    timing.part_1_stats.clone_from(&record.stats);
+                    timing.part_1_heap = record.heap;
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                2 => {
+                    timing.part_2 = duration_str;
+                    timing.part_2_stats.clone_from(&record.stats);
+                    timing.part_2_heap = record.heap;
                 }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+            timing.total_nanos += record.nanos as f64;
+        }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        timing
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{artifact_executable, collect_timing};
+        use crate::{
+            day,
+            template::{
+                protocol::{ParseRecord, PartRecord, Records},
+                stats::Stats,
+            },
+        };
+        use std::time::Duration;
+
+        fn stats(nanos: u64, samples: u128) -> Option<Stats> {
+            (samples > 1).then(|| {
+                let samples = vec![Duration::from_nanos(nanos); samples as usize];
+                Stats::from_samples(&samples).unwrap()
+            })
+        }
 
-        use crate::day;
+        fn record(part: u8, nanos: u64, samples: u128) -> PartRecord {
+            let stats = stats(nanos, samples);
+
+            PartRecord {
+                day: day!(1),
+                part,
+                answer: Some("@ ( ) ms (2s @ 5 samples)".into()),
+                error: None,
+                nanos: u128::from(nanos),
+                samples,
+                stats,
+                heap: None,
+            }
+        }
+
+        fn records(parts: Vec<PartRecord>) -> Records {
+            Records { parse: None, parts }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn collects_execution_times() {
+            let res = collect_timing(
+                &records(vec![record(1, 74, 100), record(2, 74_130_000, 10)]),
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 100);
+            assert_eq!(
+                res.part_2_stats.unwrap().median,
+                Duration::from_nanos(74_130_000)
+            );
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+        fn ignores_parts_that_were_not_benched() {
+            let res = collect_timing(
+                &records(vec![record(1, 2_000_000_000, 5), record(2, 100, 1)]),
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2_000_000_000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert!(res.part_2.is_none());
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn collects_missing_parts() {
+            let res = collect_timing(&Records::default(), day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+            assert!(res.parse.is_none());
+        }
+
+        #[test]
+        fn finds_artifact_executables() {
+            let line = r#"{"reason":"compiler-artifact","target":{"name":"2023_01","kind":["bin"]},"executable":"/repo/target/debug/2023_01","fresh":true}"#;
+            assert_eq!(
+                artifact_executable(line, "2023_01"),
+                Some("/repo/target/debug/2023_01".into())
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(artifact_executable(line, "2023_02"), None);
+
+            let lib = r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#;
+            assert_eq!(artifact_executable(lib, "advent_of_code"), None);
+            assert_eq!(
+                artifact_executable(r#"{"reason":"build-finished","success":true}"#, "2023_01"),
+                None
+            );
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let records = Records {
+                parse: Some(ParseRecord {
+                    day: day!(1),
+                    nanos: 1_500,
+                    samples: 10,
+                    stats: stats(1_500, 10),
+                    heap: None,
+                }),
+                parts: vec![record(1, 500, 10), record(2, 1_000, 10)],
+            };
+            let res = collect_timing(&records, day!(1));
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_eq!(res.parse_stats.unwrap().samples, 10);
+            assert_eq!(res.total_nanos, 3_000_f64);
         }
     }
 }
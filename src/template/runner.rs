@@ -1,70 +1,344 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::record_answers;
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::output::{out, outln};
+use crate::template::protocol::{ParseRecord, PartRecord, JSON_ARG, PART_FAILED_EXIT_CODE};
+
+use crate::template::heap::{self, HeapStats};
+pub use crate::template::protocol::Records;
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Feedback, Submission, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{BenchConfig, Params};
+use crate::template::{Puzzle, ANSI_ITALIC, ANSI_RESET};
+
+/// Prefix of the line that reports sample statistics after a benched part.
+const STATS_PREFIX: &str = "  ↳ ";
+
+/// Entry point of a solution, created by the [`solution!`](crate::solution) macro.
+/// Allows running solutions in-process, without spawning their binary.
+pub struct Solution {
+    pub puzzle: Puzzle,
+    /// Runs all parts of the solution against the provided input.
+    pub run: fn(&str, &RunOptions) -> Records,
+}
+
+/// Controls how the parts of a solution are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the parts with the provided config instead of running them once.
+    pub bench_config: Option<BenchConfig>,
+    /// Emit a machine-readable record for each part, see [`PartRecord`].
+    pub json: bool,
+    /// Submit the result of this part.
+    pub submit: Option<u8>,
+    /// Record the answers in the answer ledger, see [`record_answers`].
+    pub record: bool,
+    /// Only run this part instead of all parts of the solution.
+    pub part: Option<u8>,
+    /// Run the examples of the solution instead of its input, see [`examples`](crate::template::examples).
+    pub examples: bool,
+    /// Parameters passed to parts that accept them, see [`PartFn`].
+    pub params: Params,
+}
+
+impl RunOptions {
+    /// Parse the arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let bench_config = args.iter().any(|x| x == "--time").then(|| {
+            BenchConfig::from_args(&args).unwrap_or_else(|e| {
+                eprintln!("Invalid benchmark configuration: {e}");
+                process::exit(1);
+            })
+        });
+
+        let submit = args.iter().position(|x| x == "--submit").map(|i| {
+            let Some(Ok(part)) = args.get(i + 1).map(|x| x.parse::<u8>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let params = Params::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("Invalid parameter: {e}");
+            process::exit(1);
+        });
+
+        Self {
+            bench_config,
+            json: args.iter().any(|x| x == JSON_ARG),
+            submit,
+            record: args.iter().any(|x| x == "--record"),
+            part: None,
+            examples: args.iter().any(|x| x == "--examples"),
+            params,
+        }
+    }
+
+    /// Whether `part` should run, see [`RunOptions::part`].
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Return value of a solution part.
+///
+/// Parts return an [`Option`] if they can only be unsolved, or a [`Result`] if they can fail,
+/// e.g. because the input could not be parsed.
+pub trait PartResult {
+    /// Converts the value into the answer of the part, or the message of the error it failed with.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        self.as_ref()
+            .map(|x| Some(x.to_string()))
+            .map_err(format_error)
+    }
+}
+
+/// A solution part, which takes its input and optionally the [`Params`] of the run:
+/// `fn(input: I) -> R` or `fn(input: I, params: &Params) -> R`.
+///
+/// `M` only tells the two shapes apart, it is inferred from the function.
+pub trait PartFn<I, M> {
+    type Output: PartResult;
+
+    fn call(&self, input: I, params: &Params) -> Self::Output;
+}
+
+impl<I, R: PartResult, F: Fn(I) -> R> PartFn<I, fn(I) -> R> for F {
+    type Output = R;
+
+    fn call(&self, input: I, _: &Params) -> R {
+        self(input)
+    }
+}
+
+impl<I, R: PartResult, F: Fn(I, &Params) -> R> PartFn<I, fn(I, &Params) -> R> for F {
+    type Output = R;
+
+    fn call(&self, input: I, params: &Params) -> R {
+        self(input, params)
+    }
+}
+
+/// Formats an error along with its causes.
+fn format_error(error: impl Display) -> String {
+    // NOTE: the alternate format includes the chain of contexts of `anyhow` errors.
+    format!("{error:#}")
+}
+
+pub fn run_part<I: Clone, M>(
+    func: impl PartFn<I, M>,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> PartRecord {
     let part_str = format!("Part {part}");
+    let func = |input: I| func.call(input, &options.params);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, stats, heap) = run_timed(func, input, options, |result| {
+        let result = result.to_answer();
+        print_result(&result, &part_str, "");
+        result.is_ok()
+    });
 
+    let result = result.to_answer();
+    let samples = stats.as_ref().map(|stats| stats.samples);
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(stats) = &stats {
+        outln!("{}", format_stats(stats));
+    }
+
+    if let Some(heap) = &heap {
+        outln!("{STATS_PREFIX}heap: {heap}");
+    }
+
+    let (answer, error) = match result {
+        Ok(answer) => (answer, None),
+        Err(error) => (None, Some(error)),
+    };
+
+    let record = PartRecord {
+        day: puzzle.day,
+        part,
+        answer,
+        error,
+        nanos: duration.as_nanos(),
+        samples: samples.unwrap_or(1),
+        stats,
+        heap,
+    };
+
+    if options.json {
+        outln!("{}", record.to_json_line());
+    }
+
+    if options.submit == Some(part) && record.answer.is_some() {
+        if let Err(e) = submit_result(puzzle, &record, !options.record) {
+            eprintln!("failed to submit answer: {e}");
+        }
+    }
+
+    // NOTE: recorded after submitting, so a correct submission does not trigger a warning.
+    if options.record {
+        record_answers(puzzle.year, std::slice::from_ref(&record));
+    }
+
+    record
+}
+
+/// Runs the parser of a solution that parses its input once for both parts.
+/// The parser is benched like a part, its value is shared by the parts.
+pub fn run_parse<T, E: Display>(
+    func: impl Fn(&str) -> Result<T, E>,
+    input: &str,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> (Result<T, String>, ParseRecord) {
+    let (result, duration, stats, heap) = run_timed(func, input, options, |result| {
+        out!("Parse: ");
+        result.is_ok()
+    });
+
+    let samples = stats.as_ref().map(|stats| stats.samples);
+    let duration_str = format_duration(&duration, samples);
+
+    out!("\r");
+    match &result {
+        Ok(_) => outln!("Parse: {ANSI_ITALIC}done{ANSI_RESET}{duration_str}"),
+        Err(e) => outln!(
+            "Parse: {ANSI_BOLD}error{ANSI_RESET}: {}{duration_str}",
+            format_error(e)
+        ),
+    }
+
+    if let Some(stats) = &stats {
+        outln!("{}", format_stats(stats));
+    }
+
+    if let Some(heap) = &heap {
+        outln!("{STATS_PREFIX}heap: {heap}");
+    }
+
+    let record = ParseRecord {
+        day: puzzle.day,
+        nanos: duration.as_nanos(),
+        samples: samples.unwrap_or(1),
+        stats,
+        heap,
+    };
+
+    if options.json {
+        outln!("{}", record.to_json_line());
+    }
+
+    (result.map_err(format_error), record)
+}
+
+/// Records a part that could not run because its input failed to parse.
+pub fn skip_part(puzzle: Puzzle, part: u8, parse_error: &str, options: &RunOptions) -> PartRecord {
+    outln!("Part {part}: {ANSI_BOLD}error{ANSI_RESET}: the input could not be parsed");
+
+    let record = PartRecord {
+        day: puzzle.day,
+        part,
+        answer: None,
+        error: Some(format!("failed to parse input: {parse_error}")),
+        nanos: 0,
+        samples: 1,
+        stats: None,
+        heap: None,
+    };
+
+    if options.json {
+        outln!("{}", record.to_json_line());
+    }
+
+    record
+}
+
+/// Exits the process with [`PART_FAILED_EXIT_CODE`] if any part failed with an error.
+pub fn exit_on_failure(records: &[PartRecord]) {
+    if records.iter().any(|r| r.error.is_some()) {
+        process::exit(PART_FAILED_EXIT_CODE);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (see [`BenchConfig`] for how the number of samples is determined.)
+///
+/// The `hook` is called with the result of the first run and decides whether the function is benched, so failures are not.
+/// Allocations are counted during the first run, see [`heap::measure`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    hook: impl FnOnce(&T) -> bool,
+) -> (T, Duration, Option<Stats>, Option<HeapStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        heap::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let should_bench = hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if let (Some(config), true) = (&options.bench_config, should_bench) {
+        let stats = bench(func, input, &base_time, config);
+        (result, stats.mean, Some(stats), heap)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, heap)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Stats {
+    out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // warm up caches and branch predictors. this also gives a better estimate of the time per run than the first run.
+    let mut time_per_run = *base_time;
+    let mut warmup_runs = 0;
+    let warmup_timer = Instant::now();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    while warmup_timer.elapsed() < config.warmup {
+        black_box(func(black_box(input.clone())));
+        warmup_runs += 1;
+    }
+
+    if warmup_runs > 0 {
+        time_per_run = warmup_timer.elapsed() / warmup_runs;
+    }
+
+    let bench_iterations = config.iterations(&time_per_run);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,99 +350,164 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: `BenchConfig` guarantees at least one sample.
+    Stats::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, samples: Option<u128>) -> String {
+    match samples {
+        Some(samples) => format!(" ({duration:.1?} @ {samples} samples)"),
+        None => format!(" ({duration:.1?})"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn format_stats(stats: &Stats) -> String {
+    // flag noisy parts, their mean should be taken with a grain of salt.
+    let flag = if stats.is_noisy() { " ⚠" } else { "" };
+
+    format!(
+        "{STATS_PREFIX}median: {:.1?}, min: {:.1?}, max: {:.1?}, stddev: {:.1?}, p95: {:.1?}, p99: {:.1?}, outliers: {}{flag}",
+        stats.median, stats.min, stats.max, stats.stddev, stats.p95, stats.p99, stats.outliers
+    )
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Err(error) => {
+            if is_intermediate_result {
+                out!("{part}: {ANSI_BOLD}error{ANSI_RESET}");
+            } else {
+                out!("\r");
+                outln!("{part}: {ANSI_BOLD}error{ANSI_RESET}: {error}{duration_str}");
+            }
+        }
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Prints the result of a part that was run elsewhere, e.g. by a spawned solution.
+pub(crate) fn print_record(record: &PartRecord) {
+    let result = match &record.error {
+        Some(error) => Err(error.clone()),
+        None => Ok(record.answer.clone()),
+    };
+    let samples = record.stats.as_ref().map(|stats| stats.samples);
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    print_result(
+        &result,
+        &format!("Part {}", record.part),
+        &format_duration(&record.duration(), samples),
+    );
+}
+
+/// Try to submit one part of the solution.
+/// Answers that are known to be wrong are not submitted, see [`Submissions::check`].
+/// The feedback is stored in the submission history, correct answers are recorded in the answer ledger if `record_correct` is set.
+fn submit_result(
+    puzzle: Puzzle,
+    record: &PartRecord,
+    record_correct: bool,
+) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let (day, part) = (puzzle.day, record.part);
+    let answer = record.answer.clone().unwrap_or_default();
+
+    let mut history = Submissions::read_from_file(puzzle.year);
+    let now = submissions::now();
+    history.print_history(day, part, now);
+
+    if let Err(reason) = history.check(day, part, &answer, now) {
+        outln!("Not submitting: {reason}");
+        return Ok(());
+    }
+
+    outln!("Submitting result...");
+
+    let response = client.submit(puzzle, part, &answer)?;
+    outln!("{}", response.trim_end());
+
+    let feedback = Feedback::from_response(&response);
+    outln!("Feedback: {feedback}");
+
+    let is_correct = feedback == Feedback::Correct;
+    history.data.push(Submission {
+        day,
+        part,
+        answer,
+        feedback,
+        timestamp: now,
+    });
+
+    if let Err(e) = history.store_file(puzzle.year) {
+        eprintln!("Failed to store submission history: {e}");
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if is_correct && record_correct {
+        record_answers(puzzle.year, std::slice::from_ref(record));
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    Ok(())
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartResult;
+    use anyhow::{anyhow, Context};
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn converts_results() {
+        assert_eq!(Ok::<_, String>(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(
+            "x".parse::<u32>().to_answer(),
+            Err("invalid digit found in string".into())
+        );
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn includes_error_chains() {
+        let result: anyhow::Result<u32> =
+            Err(anyhow!("invalid tile 'x'")).context("failed to parse map");
+        assert_eq!(
+            result.to_answer(),
+            Err("failed to parse map: invalid tile 'x'".into())
+        );
+    }
 }
@@ -0,0 +1,142 @@
+//! Summary statistics over the samples collected while benchmarking a solution part.
+use std::time::Duration;
+
+/// Multiplier applied to the interquartile range to derive the outlier fences (Tukey's method).
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+
+/// Share of outliers above which a benchmark is considered noisy.
+const NOISY_OUTLIER_RATIO: f64 = 0.1;
+
+/// Statistics describing the distribution of a set of benchmark samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    /// Number of samples outside of `[Q1 - 1.5 * IQR, Q3 + 1.5 * IQR]`.
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes statistics for the provided samples, returns [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - OUTLIER_IQR_FACTOR * iqr, q3 + OUTLIER_IQR_FACTOR * iqr);
+
+        Some(Stats {
+            samples: nanos.len() as u128,
+            mean: from_nanos(mean),
+            median: from_nanos(median(&nanos)),
+            min: from_nanos(nanos[0]),
+            max: from_nanos(nanos[nanos.len() - 1]),
+            stddev: from_nanos(variance.sqrt()),
+            p95: from_nanos(percentile(&nanos, 95.0)),
+            p99: from_nanos(percentile(&nanos, 99.0)),
+            outliers: nanos.iter().filter(|&&x| x < low || x > high).count(),
+        })
+    }
+
+    /// Returns `true` if too many samples are outliers for the mean to be trustworthy.
+    pub fn is_noisy(&self) -> bool {
+        self.outliers as f64 > self.samples as f64 * NOISY_OUTLIER_RATIO
+    }
+}
+
+/// Expects `sorted` to be non-empty and sorted in ascending order.
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Nearest-rank percentile. Expects `sorted` to be non-empty and sorted in ascending order.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.max, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert_eq!(stats.p99, Duration::from_nanos(42));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&nanos(&[40, 10, 30, 20])).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(40));
+        assert_eq!(stats.stddev, Duration::from_nanos(11));
+        assert_eq!(stats.p95, Duration::from_nanos(40));
+    }
+
+    #[test]
+    fn computes_percentiles() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.p99, Duration::from_nanos(99));
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.max, Duration::from_nanos(500));
+        assert!(stats.is_noisy());
+    }
+}
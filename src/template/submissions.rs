@@ -0,0 +1,508 @@
+//! History of submitted answers, used to guard against re-submitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    process,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use tinyjson::JsonValue;
+
+use crate::template::output::outln;
+use crate::template::{Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// The feedback Advent of Code gave for a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited {
+        wait: Duration,
+    },
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl Feedback {
+    /// Interprets the response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        let response = response.to_lowercase();
+
+        if response.contains("that's the right answer") {
+            Feedback::Correct
+        } else if response.contains("you gave an answer too recently") {
+            Feedback::RateLimited {
+                wait: parse_wait(&response).unwrap_or_default(),
+            }
+        } else if response.contains("that's not the right answer") {
+            if response.contains("too high") {
+                Feedback::TooHigh
+            } else if response.contains("too low") {
+                Feedback::TooLow
+            } else {
+                Feedback::Wrong
+            }
+        } else {
+            Feedback::Unknown
+        }
+    }
+
+    /// Returns `true` if the feedback rules out the submitted answer.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Feedback::Wrong | Feedback::TooHigh | Feedback::TooLow)
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Feedback::Correct => "correct",
+            Feedback::Wrong => "wrong",
+            Feedback::TooHigh => "too_high",
+            Feedback::TooLow => "too_low",
+            Feedback::RateLimited { .. } => "rate_limited",
+            Feedback::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feedback::Correct => write!(f, "correct"),
+            Feedback::Wrong => write!(f, "wrong"),
+            Feedback::TooHigh => write!(f, "too high"),
+            Feedback::TooLow => write!(f, "too low"),
+            Feedback::RateLimited { wait } => write!(f, "rate-limited, wait {wait:?}"),
+            Feedback::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Parses the wait time of a rate-limited response, e.g. `you have 4m 30s left to wait`.
+fn parse_wait(response: &str) -> Option<Duration> {
+    let re = Regex::new(r"you have (?:(\d+)m)?\s*(?:(\d+)s)? left to wait").unwrap();
+    let captures = re.captures(response)?;
+
+    let value = |i: usize| {
+        captures
+            .get(i)
+            .and_then(|x| x.as_str().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+
+    Some(Duration::from_secs(value(1) * 60 + value(2)))
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(year.data_dir().join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Self {
+        let path = year.data_dir().join(SUBMISSIONS_FILE_NAME);
+        let Ok(s) = fs::read_to_string(&path) else {
+            return Submissions::default();
+        };
+
+        match Submissions::try_from(s) {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("Failed to read \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Submissions for a part, in order of submission.
+    pub fn history(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Returns `true` if `answer` was submitted for a part and marked correct.
+    pub fn is_correct(&self, day: Day, part: u8, answer: &str) -> bool {
+        self.history(day, part)
+            .any(|s| s.answer == answer && s.feedback == Feedback::Correct)
+    }
+
+    /// Checks whether `answer` is worth submitting at `now`, returns the reason if it is not.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        if let Some(correct) = self
+            .history(day, part)
+            .find(|s| s.feedback == Feedback::Correct)
+        {
+            return Err(format!(
+                "part {part} was already solved with `{}`.",
+                correct.answer
+            ));
+        }
+
+        // NOTE: the rate limit applies to the whole account, not only to the part that was submitted.
+        if let Some(Submission {
+            feedback: Feedback::RateLimited { wait },
+            timestamp,
+            ..
+        }) = self
+            .data
+            .iter()
+            .filter(|s| matches!(s.feedback, Feedback::RateLimited { .. }))
+            .max_by_key(|s| s.timestamp)
+        {
+            let remaining = (timestamp + wait.as_secs()).saturating_sub(now);
+            if remaining > 0 {
+                return Err(format!("rate-limited for another {remaining}s."));
+            }
+        }
+
+        if let Some(previous) = self
+            .history(day, part)
+            .find(|s| s.answer == answer && s.feedback.is_wrong())
+        {
+            return Err(format!(
+                "`{answer}` was already submitted and was {}.",
+                previous.feedback
+            ));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bounds = self
+                .history(day, part)
+                .filter_map(|s| s.answer.parse::<i128>().ok().map(|x| (x, &s.feedback)));
+
+            for (bound, feedback) in bounds {
+                match feedback {
+                    Feedback::TooHigh if value >= bound => {
+                        return Err(format!("`{answer}` is ruled out, `{bound}` was too high."));
+                    }
+                    Feedback::TooLow if value <= bound => {
+                        return Err(format!("`{answer}` is ruled out, `{bound}` was too low."));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints all previous submissions for a part.
+    pub fn print_history(&self, day: Day, part: u8, now: u64) {
+        let history: Vec<&Submission> = self.history(day, part).collect();
+
+        if history.is_empty() {
+            return;
+        }
+
+        outln!("Previous submissions for day {day}, part {part}:");
+        for submission in history {
+            outln!(
+                "  `{}`: {} ({} ago)",
+                submission.answer,
+                submission.feedback,
+                format_age(now.saturating_sub(submission.timestamp))
+            );
+        }
+    }
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "feedback".into(),
+            JsonValue::String(value.feedback.key().into()),
+        );
+
+        if let Feedback::RateLimited { wait } = value.feedback {
+            map.insert("wait_secs".into(), JsonValue::Number(wait.as_secs() as f64));
+        }
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let feedback = match json.get("feedback").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => Feedback::Correct,
+            Some(x) if x == "wrong" => Feedback::Wrong,
+            Some(x) if x == "too_high" => Feedback::TooHigh,
+            Some(x) if x == "too_low" => Feedback::TooLow,
+            Some(x) if x == "rate_limited" => Feedback::RateLimited {
+                wait: Duration::from_secs(number("wait_secs")? as u64),
+            },
+            Some(x) if x == "unknown" => Feedback::Unknown,
+            _ => return Err("Expected submission.feedback to be a known feedback.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part: number("part")? as u8,
+            answer: answer.clone(),
+            feedback,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Feedback, Submission, Submissions};
+    use crate::day;
+
+    fn submission(answer: &str, feedback: Feedback, timestamp: u64) -> Submission {
+        Submission {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            feedback,
+            timestamp,
+        }
+    }
+
+    mod feedback {
+        use super::Feedback;
+        use std::time::Duration;
+
+        #[test]
+        fn parses_responses() {
+            assert_eq!(
+                Feedback::from_response("That's the right answer! You are one gold star closer."),
+                Feedback::Correct
+            );
+            assert_eq!(
+                Feedback::from_response("That's not the right answer; your answer is too high."),
+                Feedback::TooHigh
+            );
+            assert_eq!(
+                Feedback::from_response("That's not the right answer; your answer is too low."),
+                Feedback::TooLow
+            );
+            assert_eq!(
+                Feedback::from_response("That's not the right answer. If you're stuck, ..."),
+                Feedback::Wrong
+            );
+            assert_eq!(
+                Feedback::from_response("You don't seem to be solving the right level."),
+                Feedback::Unknown
+            );
+        }
+
+        #[test]
+        fn parses_wait_times() {
+            assert_eq!(
+                Feedback::from_response(
+                    "You gave an answer too recently. You have 4m 30s left to wait."
+                ),
+                Feedback::RateLimited {
+                    wait: Duration::from_secs(270)
+                }
+            );
+            assert_eq!(
+                Feedback::from_response(
+                    "You gave an answer too recently. You have 42s left to wait."
+                ),
+                Feedback::RateLimited {
+                    wait: Duration::from_secs(42)
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let submissions = Submissions {
+            data: vec![
+                submission("100", Feedback::TooHigh, 0),
+                submission("10", Feedback::TooLow, 0),
+                submission("50", Feedback::Wrong, 0),
+            ],
+        };
+
+        assert!(submissions.check(day!(1), 1, "50", 0).is_err());
+        assert!(submissions.check(day!(1), 1, "100", 0).is_err());
+        assert!(submissions.check(day!(1), 1, "120", 0).is_err());
+        assert!(submissions.check(day!(1), 1, "-3", 0).is_err());
+        assert!(submissions.check(day!(1), 1, "42", 0).is_ok());
+        assert!(submissions.check(day!(1), 2, "120", 0).is_ok());
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = Submissions {
+            data: vec![submission("42", Feedback::Correct, 0)],
+        };
+        assert!(submissions.check(day!(1), 1, "43", 0).is_err());
+
+        assert!(submissions.is_correct(day!(1), 1, "42"));
+        assert!(!submissions.is_correct(day!(1), 1, "43"));
+        assert!(!submissions.is_correct(day!(1), 2, "42"));
+    }
+
+    #[test]
+    fn respects_rate_limits() {
+        let wait = Duration::from_secs(60);
+        let submissions = Submissions {
+            data: vec![submission("42", Feedback::RateLimited { wait }, 100)],
+        };
+        assert!(submissions.check(day!(1), 1, "42", 130).is_err());
+        assert!(submissions.check(day!(1), 1, "42", 160).is_ok());
+
+        let submissions = Submissions {
+            data: vec![
+                submission("42", Feedback::RateLimited { wait }, 100),
+                Submission {
+                    day: day!(2),
+                    ..submission("7", Feedback::Wrong, 120)
+                },
+            ],
+        };
+        assert!(submissions.check(day!(1), 2, "42", 130).is_err());
+        assert!(submissions.check(day!(2), 1, "8", 130).is_err());
+        assert!(submissions.check(day!(2), 1, "8", 160).is_ok());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = Submissions {
+            data: vec![
+                submission("1", Feedback::TooLow, 1_700_000_000),
+                submission(
+                    "2",
+                    Feedback::RateLimited {
+                        wait: Duration::from_secs(30),
+                    },
+                    1_700_000_060,
+                ),
+            ],
+        };
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, submissions.data);
+    }
+}
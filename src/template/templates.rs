@@ -0,0 +1,149 @@
+//! Templates of new solutions, see `cargo scaffold <day> --template <name>`.
+//!
+//! Templates are built in or read from `templates/<name>.txt` in the project directory, which take precedence over
+//! built-in templates of the same name.
+use std::{fs, path::Path};
+
+use regex::{Captures, Regex};
+
+use crate::template::Puzzle;
+
+/// Directory of user templates, relative to the project directory.
+pub const USER_TEMPLATES_DIR: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILT_IN_TEMPLATES: [(&str, &str); 3] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse-solve",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse-solve.txt"
+        )),
+    ),
+];
+
+/// Names of all templates, built in and user templates, in alphabetical order.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN_TEMPLATES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATES_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            Some(name.strip_suffix(".txt")?.to_string())
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Reads the template `name`, preferring a user template over a built-in one.
+pub fn load(name: &str) -> Result<String, String> {
+    let path = Path::new(USER_TEMPLATES_DIR).join(format!("{name}.txt"));
+    if path.exists() {
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("could not read template {}: {e}", path.display()));
+    }
+
+    match BUILT_IN_TEMPLATES.iter().find(|(n, _)| *n == name) {
+        Some((_, template)) => Ok(template.to_string()),
+        None => Err(format!(
+            "unknown template `{name}`, expected one of: {}.",
+            names().join(", ")
+        )),
+    }
+}
+
+/// Replaces the placeholders of a template, e.g. `%DAY%`. `title` is left empty if the puzzle was not downloaded.
+pub fn render(template: &str, puzzle: Puzzle, title: Option<&str>) -> Result<String, String> {
+    let placeholder_re = Regex::new(r"%([A-Z][A-Z0-9_]*)%").unwrap();
+    let example_path = |part: u8| {
+        puzzle
+            .data_path("examples", &format!("-{part}"), "txt")
+            .display()
+            .to_string()
+    };
+
+    let mut unknown = vec![];
+    let rendered = placeholder_re.replace_all(template, |captures: &Captures| match &captures[1] {
+        "DAY_NUMBER" => puzzle.day.into_inner().to_string(),
+        "DAY" => puzzle.day.to_string(),
+        "YEAR" => puzzle.year.to_string(),
+        "TITLE" => title.unwrap_or_default().to_string(),
+        "EXAMPLES" => puzzle
+            .data_path("examples", "", "toml")
+            .display()
+            .to_string(),
+        "EXAMPLE_1" => example_path(1),
+        "EXAMPLE_2" => example_path(2),
+        _ => {
+            unknown.push(captures[0].to_string());
+            captures[0].to_string()
+        }
+    });
+
+    if unknown.is_empty() {
+        Ok(rendered.into_owned())
+    } else {
+        Err(format!("unknown placeholder(s) {}.", unknown.join(", ")))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render};
+    use crate::{day, template::Puzzle};
+
+    fn puzzle() -> Puzzle {
+        Puzzle::new("2023".parse().unwrap(), day!(1))
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "// %TITLE% (%YEAR%, day %DAY%)\nsolution!(%DAY_NUMBER%);\n// %EXAMPLE_2%, %EXAMPLES%\nx % 2",
+            puzzle(),
+            Some("Trebuchet?!"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "// Trebuchet?! (2023, day 01)\nsolution!(1);\n\
+            // data/2023/examples/01-2.txt, data/2023/examples/01.toml\nx % 2"
+        );
+        assert_eq!(render("// %TITLE%", puzzle(), None).unwrap(), "// ");
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        let error = render("%DAY% %DAYS% %PART%", puzzle(), None).unwrap_err();
+        assert!(error.contains("%DAYS%, %PART%"));
+
+        assert!(load("grid").unwrap().contains("Grid<char>"));
+        assert!(load("nope")
+            .unwrap_err()
+            .contains("default, grid, parse-solve"));
+    }
+}
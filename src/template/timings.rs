@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::heap::HeapStats;
+use crate::template::stats::Stats;
+use crate::template::{Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Number of runs without a label that are kept in the history of every day. Labelled runs are always kept.
+pub const MAX_UNLABELLED_RUNS: usize = 20;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,27 +16,79 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Time spent parsing the input, for solutions that parse it once for both parts.
+    pub parse: Option<String>,
+    pub parse_stats: Option<Stats>,
+    /// Allocations of each phase, if the solution was benched with the `heap-stats` feature.
+    pub part_1_heap: Option<HeapStats>,
+    pub part_2_heap: Option<HeapStats>,
+    pub parse_heap: Option<HeapStats>,
     pub total_nanos: f64,
 }
 
+/// A stored benchmark of a single day, along with the state of the repository it was taken at.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub timing: Timing,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the `HEAD` commit, if the repository is a git repository.
+    pub commit: Option<String>,
+    /// Name of the baseline this run was stored as, if any.
+    pub label: Option<String>,
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// Latest timing of every day.
     pub data: Vec<Timing>,
+    /// Every stored run, oldest first.
+    pub history: Vec<Run>,
+}
+
+/// Change of the median time of a part between a baseline and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        let before = self.before.as_nanos() as f64;
+        let after = self.after.as_nanos() as f64;
+        if before == 0.0 {
+            return 0.0;
+        }
+        (after - before) / before * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(year.data_dir())?;
+        let mut file = fs::File::create(year.data_dir().join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        let path = year.data_dir().join(TIMINGS_FILE_NAME);
+        let s = fs::read_to_string(&path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -44,7 +101,7 @@ impl Timings {
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present. The history of `self` is kept.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -58,8 +115,96 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+        Timings {
+            data,
+            history: self.history.clone(),
+        }
+    }
+
+    /// Appends the timings of `new` to the history, dropping the oldest unlabelled runs of a day
+    /// beyond [`MAX_UNLABELLED_RUNS`].
+    pub fn push_history(
+        &mut self,
+        new: &Self,
+        timestamp: u64,
+        commit: Option<&str>,
+        label: Option<&str>,
+    ) {
+        for timing in &new.data {
+            self.history.push(Run {
+                timing: timing.clone(),
+                timestamp,
+                commit: commit.map(Into::into),
+                label: label.map(Into::into),
+            });
+        }
+
+        let mut unlabelled: HashMap<Day, usize> = HashMap::new();
+        let mut kept: Vec<Run> = self
+            .history
+            .drain(..)
+            .rev()
+            .filter(|run| {
+                let count = unlabelled.entry(run.timing.day).or_default();
+                if run.label.is_none() {
+                    *count += 1;
+                }
+                run.label.is_some() || *count <= MAX_UNLABELLED_RUNS
+            })
+            .collect();
+        kept.reverse();
+        self.history = kept;
+    }
+
+    /// Finds the timing a new run of `day` is compared against.
+    /// Without a `reference`, this is the latest stored timing of the day. Otherwise, it is the latest
+    /// run that was stored with `reference` as its label or at a commit starting with `reference`.
+    pub fn baseline(&self, day: Day, reference: Option<&str>) -> Option<&Timing> {
+        let run = self.history.iter().rev().find(|run| {
+            run.timing.day == day
+                && reference.is_none_or(|r| {
+                    run.label.as_deref() == Some(r)
+                        || run.commit.as_deref().is_some_and(|c| c.starts_with(r))
+                })
+        });
+
+        match (run, reference) {
+            (Some(run), _) => Some(&run.timing),
+            // NOTE: timings stored by older versions have no history.
+            (None, None) => self.data.iter().find(|t| t.day == day),
+            (None, Some(_)) => None,
+        }
+    }
+
+    /// Compares the median times of all parts of `new` with their baseline.
+    /// Parts without statistics on either side are skipped.
+    pub fn compare(&self, new: &Self, reference: Option<&str>) -> Vec<Comparison> {
+        let mut comparisons = vec![];
+
+        for timing in &new.data {
+            let Some(baseline) = self.baseline(timing.day, reference) else {
+                continue;
+            };
+
+            let parts = [
+                (1, &baseline.part_1_stats, &timing.part_1_stats),
+                (2, &baseline.part_2_stats, &timing.part_2_stats),
+            ];
+
+            for (part, before, after) in parts {
+                if let (Some(before), Some(after)) = (before, after) {
+                    comparisons.push(Comparison {
+                        day: timing.day,
+                        part,
+                        before: before.median,
+                        after: after.median,
+                    });
+                }
+            }
+        }
+
+        comparisons
     }
 
     /// Sum up total duration of timings as millis.
@@ -85,6 +230,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -95,19 +245,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: the history is optional to stay compatible with timings stored by older versions.
+        let json_history = match json.get("history") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -140,6 +305,49 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, heap) in [
+            ("part_1_heap", &value.part_1_heap),
+            ("part_2_heap", &value.part_2_heap),
+            ("parse_heap", &value.parse_heap),
+        ] {
+            map.insert(
+                key.into(),
+                heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,10 +381,48 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: statistics are optional to stay compatible with timings stored by older versions.
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        // NOTE: the parse time is optional, most solutions parse their input in each part.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let parse_stats = match json.get("parse_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let heap = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => HeapStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            parse,
+            parse_stats,
+            part_1_heap: heap("part_1_heap")?,
+            part_2_heap: heap("part_2_heap")?,
+            parse_heap: heap("parse_heap")?,
             total_nanos,
         })
     }
@@ -184,6 +430,124 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut json = JsonValue::from(&value.timing);
+
+        if let JsonValue::Object(map) = &mut json {
+            map.insert(
+                "timestamp".into(),
+                JsonValue::Number(value.timestamp as f64),
+            );
+            map.insert(
+                "commit".into(),
+                value
+                    .commit
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            );
+            map.insert(
+                "label".into(),
+                value
+                    .label
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        json
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let timing = Timing::try_from(value)?;
+
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected run.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        Ok(Run {
+            timing,
+            timestamp: *timestamp as u64,
+            commit: optional_string("commit")?,
+            label: optional_string("label")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("stddev_nanos".into(), nanos(value.stddev));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("p99_nanos".into(), nanos(value.p99));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            p95: duration("p95_nanos")?,
+            p99: duration("p99_nanos")?,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -197,26 +561,49 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 4e+10,
                 },
             ],
+            history: vec![],
         }
     }
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
@@ -230,6 +617,19 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "mean_nanos": 1000000, "median_nanos": 900000, "min_nanos": 800000, "max_nanos": 2000000, "stddev_nanos": 5000, "p95_nanos": 1500000, "p99_nanos": 1900000, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, Duration::from_micros(900));
+            assert_eq!(stats.p99, Duration::from_micros(1900));
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -288,11 +688,19 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -302,11 +710,19 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -316,11 +732,19 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 0.0,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
@@ -340,8 +764,16 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 0_f64,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -360,8 +792,16 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    parse_heap: None,
                     total_nanos: 0_f64,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -388,4 +828,136 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+    mod history {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                stats::Stats,
+                timings::{Timing, Timings, MAX_UNLABELLED_RUNS},
+            },
+        };
+
+        fn timing(median_micros: u64) -> Timing {
+            let median = Duration::from_micros(median_micros);
+            Timing {
+                day: day!(10),
+                part_1: Some(format!("{median:.1?}")),
+                part_2: None,
+                part_1_stats: Some(Stats {
+                    samples: 10,
+                    mean: median,
+                    median,
+                    min: median,
+                    max: median,
+                    stddev: Duration::ZERO,
+                    p95: median,
+                    p99: median,
+                    outliers: 0,
+                }),
+                part_2_stats: None,
+                parse: None,
+                parse_stats: None,
+                part_1_heap: None,
+                part_2_heap: None,
+                parse_heap: None,
+                total_nanos: median.as_nanos() as f64,
+            }
+        }
+
+        fn timings(median_micros: u64) -> Timings {
+            Timings {
+                data: vec![timing(median_micros)],
+                history: vec![],
+            }
+        }
+
+        fn stored() -> Timings {
+            let mut stored = Timings::default();
+            for (i, (micros, commit, label)) in [
+                (100, "abc1234", Some("fast")),
+                (300, "def5678", None),
+                (120, "0123abc", None),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let new = timings(micros);
+                stored = stored.merge(&new);
+                stored.push_history(&new, i as u64, Some(commit), label);
+            }
+            stored
+        }
+
+        #[test]
+        fn keeps_history_when_merging() {
+            let stored = stored();
+            assert_eq!(stored.data.len(), 1);
+            assert_eq!(stored.history.len(), 3);
+            assert_eq!(stored.history[1].commit.as_deref(), Some("def5678"));
+        }
+
+        #[test]
+        fn caps_unlabelled_runs() {
+            let mut stored = stored();
+            for i in 0..MAX_UNLABELLED_RUNS {
+                stored.push_history(&timings(200), 10 + i as u64, None, None);
+            }
+
+            assert_eq!(stored.history.len(), MAX_UNLABELLED_RUNS + 1);
+            assert_eq!(stored.history[0].label.as_deref(), Some("fast"));
+            assert_eq!(stored.history[1].timestamp, 10);
+        }
+
+        #[test]
+        fn roundtrips_history() {
+            let json = tinyjson::JsonValue::from(stored()).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 3);
+            assert_eq!(timings.history[0].label.as_deref(), Some("fast"));
+            assert_eq!(timings.history[1].label, None);
+            assert_eq!(timings.history[2].timestamp, 2);
+        }
+
+        #[test]
+        fn finds_baselines() {
+            let stored = stored();
+            let median = |t: Option<&Timing>| t.unwrap().part_1_stats.as_ref().unwrap().median;
+            assert_eq!(
+                median(stored.baseline(day!(10), None)),
+                Duration::from_micros(120)
+            );
+            assert_eq!(
+                median(stored.baseline(day!(10), Some("fast"))),
+                Duration::from_micros(100)
+            );
+            assert_eq!(
+                median(stored.baseline(day!(10), Some("def"))),
+                Duration::from_micros(300)
+            );
+            assert!(stored.baseline(day!(10), Some("slow")).is_none());
+            assert!(stored.baseline(day!(1), None).is_none());
+        }
+
+        #[test]
+        fn falls_back_to_latest_timings_without_history() {
+            let stored = timings(100);
+            assert!(stored.baseline(day!(10), None).is_some());
+            assert!(stored.baseline(day!(10), Some("abc")).is_none());
+        }
+
+        #[test]
+        fn detects_regressions() {
+            let stored = stored();
+            let comparisons = stored.compare(&timings(360), None);
+            assert_eq!(comparisons.len(), 1);
+            assert_eq!(comparisons[0].part, 1);
+            assert_eq!(comparisons[0].change_percent(), 200.0);
+            assert!(comparisons[0].is_regression(10.0));
+
+            let comparisons = stored.compare(&timings(320), Some("def5678"));
+            assert!(!comparisons[0].is_regression(10.0));
+        }
+    }
 }
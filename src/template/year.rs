@@ -0,0 +1,178 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+const YEAR_ENV: &str = "AOC_YEAR";
+
+/// A year of Advent of Code (i.e. an integer starting at 2015).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year with an event,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        env::var(YEAR_ENV).ok().and_then(|x| x.parse().ok())
+    }
+
+    /// Reads the year from the path of a solution file named `<year>_<day>.rs`.
+    /// Used by the [`solution!`](crate::solution) macro, fails to compile for other file names.
+    pub const fn from_solution_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        // find the start of the file name, both separators are used depending on the platform.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        if bytes.len() < start + 5 || bytes[start + 4] != b'_' {
+            panic!("solution files must be named `<year>_<day>.rs`, e.g. `2023_01.rs`");
+        }
+
+        let mut year: u16 = 0;
+        let mut i = start;
+        while i < start + 4 {
+            let digit = bytes[i];
+            if !digit.is_ascii_digit() {
+                panic!("solution files must be named `<year>_<day>.rs`, e.g. `2023_01.rs`");
+            }
+            year = year * 10 + (digit - b'0') as u16;
+            i += 1;
+        }
+
+        if year < 2015 {
+            panic!("invalid year in solution file name, expecting 2015 or later");
+        }
+
+        Self(year)
+    }
+
+    /// Directory that holds the data of this year, e.g. `data/2023`.
+    pub fn data_dir(self) -> PathBuf {
+        PathBuf::from("data").join(self.to_string())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies the puzzle of a day in a given year.
+///
+/// # Display
+/// This value displays as `<year>_<day>`, which is also the name of the solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of the solution file, e.g. `./src/bin/2023_01.rs`.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2023/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, suffix: &str, extension: &str) -> PathBuf {
+        self.year
+            .data_dir()
+            .join(folder)
+            .join(format!("{}{suffix}.{extension}", self.day))
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Puzzle, Year};
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_solution_path() {
+        assert_eq!(Year::from_solution_path("src/bin/2022_01.rs"), Year(2022));
+        assert_eq!(
+            Year::from_solution_path("C:\\repo\\src\\bin\\2023_25.rs"),
+            Year(2023)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_legacy_solution_path() {
+        Year::from_solution_path("src/bin/01.rs");
+    }
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = Puzzle::new(Year(2023), day!(7));
+        assert_eq!(puzzle.to_string(), "2023_07");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2023_07.rs");
+        assert_eq!(
+            puzzle.data_path("examples", "-1", "txt"),
+            PathBuf::from("data/2023/examples/07-1.txt")
+        );
+    }
+}